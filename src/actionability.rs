@@ -0,0 +1,100 @@
+use std::time::Duration;
+
+use crate::handler::REQUEST_TIMEOUT;
+use crate::wait::IS_VISIBLE;
+
+/// Configures the checks that are performed before an element is interacted
+/// with.
+///
+/// Before clicking, the element must be attached to the document, visible,
+/// have a stable bounding box across two animation frames, be enabled and
+/// receive pointer events at the click point. Before typing, the element must
+/// be attached, visible, enabled and editable.
+///
+/// All checks are retried until they pass or the `timeout` is exceeded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionOptions {
+    /// Skip all actionability checks and perform the action right away.
+    pub force: bool,
+    /// How long to wait at most for the element to become actionable, defaults
+    /// to 30 seconds.
+    pub timeout: Duration,
+}
+
+impl ActionOptions {
+    pub fn force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for ActionOptions {
+    fn default() -> Self {
+        Self {
+            force: false,
+            timeout: Duration::from_millis(REQUEST_TIMEOUT),
+        }
+    }
+}
+
+impl From<Duration> for ActionOptions {
+    fn from(timeout: Duration) -> Self {
+        ActionOptions::default().timeout(timeout)
+    }
+}
+
+/// How long to wait before re-checking an element that is not actionable yet,
+/// the last value is used for all following attempts.
+pub(crate) const RETRY_BACKOFF_MS: [u64; 5] = [0, 20, 50, 100, 500];
+
+/// Checks the state of the `this` element and returns the name of the first
+/// state that is not satisfied, or `null` if the element is actionable.
+///
+/// Stability is checked by comparing the bounding box across two consecutive
+/// animation frames.
+const ACTIONABILITY_CHECK: &str = "async function(checkStable, checkEditable) {
+    if (!this.isConnected) return 'attached';
+    if (this.nodeType !== Node.ELEMENT_NODE) return 'visible';
+    const isVisible = () => (IS_VISIBLE)(this);
+    if (!isVisible()) return 'visible';
+    if (checkStable) {
+        const rect = () => {
+            const r = this.getBoundingClientRect();
+            return [r.x, r.y, r.width, r.height].join();
+        };
+        const raf = () => new Promise(resolve => requestAnimationFrame(resolve));
+        await raf();
+        const before = rect();
+        await raf();
+        if (!this.isConnected) return 'attached';
+        if (before !== rect()) return 'stable';
+    }
+    if (this.matches(':disabled') || this.getAttribute('aria-disabled') === 'true')
+        return 'enabled';
+    if (checkEditable) {
+        const editable = ['INPUT', 'TEXTAREA', 'SELECT'].includes(this.nodeName)
+            ? !this.readOnly
+            : this.isContentEditable;
+        if (!editable) return 'editable';
+    }
+    return null;
+}";
+
+/// The function declaration of the actionability check
+pub(crate) fn actionability_check() -> String {
+    ACTIONABILITY_CHECK.replace("IS_VISIBLE", IS_VISIBLE)
+}
+
+/// Whether the `hit` node, as returned by hit-testing, is the `this` element
+/// or one of its descendants, including those in shadow roots.
+pub(crate) const HIT_TARGET_CHECK: &str = "function(hit) {
+    for (let node = hit; node; node = node.parentNode || node.host) {
+        if (node === this) return true;
+    }
+    return false;
+}";
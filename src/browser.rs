@@ -14,9 +14,16 @@ use chromiumoxide_types::*;
 
 use crate::cmd::{to_command_response, CommandMessage};
 use crate::conn::Connection;
+use crate::dialog::DialogPolicy;
 use crate::error::{CdpError, Result};
-use crate::handler::{Handler, HandlerMessage};
+use crate::handler::viewport::Viewport;
+use crate::handler::{Handler, HandlerConfig, HandlerMessage};
 use crate::page::Page;
+use crate::storage::StorageState;
+use chromiumoxide_cdp::cdp::browser_protocol::network::{Cookie, CookieParam};
+use chromiumoxide_cdp::cdp::browser_protocol::storage::{
+    ClearCookiesParams, GetCookiesParams, SetCookiesParams,
+};
 use chromiumoxide_cdp::cdp::browser_protocol::target::CreateTargetParams;
 use chromiumoxide_cdp::cdp::CdpEventMessage;
 
@@ -37,12 +44,21 @@ pub struct Browser {
 impl Browser {
     /// Connect to an already running chromium instance via websocket
     pub async fn connect(debug_ws_url: impl Into<String>) -> Result<(Self, Handler)> {
+        Self::connect_with_config(debug_ws_url, HandlerConfig::default()).await
+    }
+
+    /// Connect to an already running chromium instance via websocket and
+    /// configure the `Handler` with the `config`
+    pub async fn connect_with_config(
+        debug_ws_url: impl Into<String>,
+        config: HandlerConfig,
+    ) -> Result<(Self, Handler)> {
         let debug_ws_url = debug_ws_url.into();
         let conn = Connection::<CdpEventMessage>::connect(&debug_ws_url).await?;
 
         let (tx, rx) = channel(1);
 
-        let fut = Handler::new(conn, rx);
+        let fut = Handler::new(conn, rx, config);
         let browser = Self {
             sender: tx,
             config: None,
@@ -75,7 +91,7 @@ impl Browser {
 
         let (tx, rx) = channel(1);
 
-        let fut = Handler::new(conn, rx);
+        let fut = Handler::new(conn, rx, config.handler_config());
 
         let browser = Self {
             sender: tx,
@@ -109,6 +125,26 @@ impl Browser {
         rx.await?
     }
 
+    /// Create a new page with the cookies and web storage of the
+    /// `StorageState`.
+    ///
+    /// The page is created blank and the state is restored before it navigates
+    /// to the url of the `params`.
+    pub async fn new_page_with_storage_state(
+        &self,
+        params: impl Into<CreateTargetParams>,
+        state: &StorageState,
+    ) -> Result<Page> {
+        let mut params = params.into();
+        let url = std::mem::replace(&mut params.url, "about:blank".to_string());
+        let page = self.new_page(params).await?;
+        page.restore_storage_state(state).await?;
+        if url != "about:blank" {
+            page.goto(url).await?;
+        }
+        Ok(page)
+    }
+
     pub async fn new_blank_tab(&self) -> anyhow::Result<Page> {
         Ok(self
             .new_page(CreateTargetParams::new("about:blank"))
@@ -129,6 +165,28 @@ impl Browser {
         to_command_response::<T>(resp, method)
     }
 
+    /// Returns the cookies of all pages of the browser's default context
+    pub async fn get_all_cookies(&self) -> Result<Vec<Cookie>> {
+        Ok(self
+            .execute(GetCookiesParams::default())
+            .await?
+            .result
+            .cookies)
+    }
+
+    /// Sets the cookies in the browser's default context, each cookie needs
+    /// either an `url` or a `domain`.
+    pub async fn set_cookies(&self, cookies: Vec<CookieParam>) -> Result<&Self> {
+        self.execute(SetCookiesParams::new(cookies)).await?;
+        Ok(self)
+    }
+
+    /// Deletes all cookies of the browser's default context
+    pub async fn clear_cookies(&self) -> Result<&Self> {
+        self.execute(ClearCookiesParams::default()).await?;
+        Ok(self)
+    }
+
     /// Return all of the pages of the browser
     pub async fn pages(&self) -> Result<Vec<Page>> {
         let (tx, rx) = oneshot_channel();
@@ -201,6 +259,15 @@ pub struct BrowserConfig {
 
     /// Data dir for user data
     pub user_data_dir: Option<PathBuf>,
+
+    /// How javascript dialogs are responded to that are not handled via
+    /// `Page::dialogs`.
+    dialog_policy: Option<DialogPolicy>,
+
+    /// The viewport new pages are emulated with, 800x600 by default.
+    ///
+    /// If `None`, pages use the size of the browser window.
+    viewport: Option<Viewport>,
 }
 
 #[derive(Debug, Clone)]
@@ -213,6 +280,8 @@ pub struct BrowserConfigBuilder {
     extensions: Vec<String>,
     process_envs: Option<HashMap<String, String>>,
     user_data_dir: Option<PathBuf>,
+    dialog_policy: Option<DialogPolicy>,
+    viewport: Option<Viewport>,
 }
 
 impl BrowserConfig {
@@ -223,6 +292,14 @@ impl BrowserConfig {
     pub fn with_executable(path: impl AsRef<Path>) -> Self {
         Self::builder().chrome_executable(path).build().unwrap()
     }
+
+    /// The config for the `Handler` that drives the launched instance
+    pub(crate) fn handler_config(&self) -> HandlerConfig {
+        HandlerConfig {
+            dialog_policy: self.dialog_policy,
+            viewport: self.viewport.clone(),
+        }
+    }
 }
 
 impl Default for BrowserConfigBuilder {
@@ -236,6 +313,8 @@ impl Default for BrowserConfigBuilder {
             extensions: Vec::new(),
             process_envs: None,
             user_data_dir: None,
+            dialog_policy: None,
+            viewport: Some(Default::default()),
         }
     }
 }
@@ -301,6 +380,20 @@ impl BrowserConfigBuilder {
         self
     }
 
+    /// Automatically accept or dismiss all javascript dialogs that are not
+    /// handled via `Page::dialogs`, so they don't block unattended pages.
+    pub fn dialog_policy(mut self, policy: DialogPolicy) -> Self {
+        self.dialog_policy = Some(policy);
+        self
+    }
+
+    /// The viewport new pages are emulated with, `None` to use the size of
+    /// the browser window instead.
+    pub fn viewport(mut self, viewport: impl Into<Option<Viewport>>) -> Self {
+        self.viewport = viewport.into();
+        self
+    }
+
     pub fn build(self) -> std::result::Result<BrowserConfig, String> {
         let executable = if let Some(e) = self.executable {
             e
@@ -317,6 +410,8 @@ impl BrowserConfigBuilder {
             extensions: self.extensions,
             process_envs: None,
             user_data_dir: None,
+            dialog_policy: self.dialog_policy,
+            viewport: self.viewport,
         })
     }
 }
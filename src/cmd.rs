@@ -10,7 +10,9 @@ use chromiumoxide_types::{Command, CommandResponse, Method, Request, Response};
 
 use crate::error::{CdpError, DeadlineExceeded, Result};
 use crate::handler::REQUEST_TIMEOUT;
-use chromiumoxide_cdp::cdp::browser_protocol::page::NavigateParams;
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    NavigateParams, NavigateToHistoryEntryParams, ReloadParams,
+};
 use chromiumoxide_cdp::cdp::browser_protocol::target::SessionId;
 
 /// Deserialize a response
@@ -54,9 +56,15 @@ impl<T> CommandMessage<T> {
         })
     }
 
-    /// Whether this command is a navigation
+    /// Whether this command is a navigation that is only complete once the
+    /// page is loaded
     pub fn is_navigation(&self) -> bool {
-        self.method.as_ref() == NavigateParams::IDENTIFIER
+        matches!(
+            self.method.as_ref(),
+            NavigateParams::IDENTIFIER
+                | ReloadParams::IDENTIFIER
+                | NavigateToHistoryEntryParams::IDENTIFIER
+        )
     }
 
     pub fn with_session<C: Command>(
@@ -0,0 +1,162 @@
+use std::sync::Arc;
+
+use chromiumoxide_cdp::cdp::browser_protocol::log::{EventEntryAdded, LogEntryLevel};
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+use chromiumoxide_cdp::cdp::js_protocol::runtime::{
+    ConsoleApiCalledType, EventConsoleApiCalled, EventExceptionThrown, ExceptionDetails, StackTrace,
+};
+
+use crate::handler::PageInner;
+use crate::js::JsHandle;
+
+/// A message that was logged to the console of a page, either by a call to
+/// the `console` API or by the browser itself, e.g. on network errors.
+#[derive(Debug, Clone)]
+pub struct ConsoleMessage {
+    /// The severity of the message
+    pub level: LogEntryLevel,
+    /// The text of the message, for `console` calls this is the concatenation
+    /// of all the arguments
+    pub text: String,
+    /// The arguments passed to the `console` call
+    pub args: Vec<JsHandle>,
+    /// Where the message originated from
+    pub location: Option<ConsoleMessageLocation>,
+    /// The stack trace at the time the message was logged, if available
+    pub stack: Option<StackTrace>,
+}
+
+impl ConsoleMessage {
+    pub(crate) fn from_event(tab: &Arc<PageInner>, event: CdpEvent) -> Option<Self> {
+        match event {
+            CdpEvent::RuntimeConsoleApiCalled(ev) => Some(Self::from_console_api_called(tab, ev)),
+            CdpEvent::LogEntryAdded(ev) => Some(Self::from_entry_added(tab, *ev)),
+            _ => None,
+        }
+    }
+
+    fn from_console_api_called(tab: &Arc<PageInner>, event: EventConsoleApiCalled) -> Self {
+        let args: Vec<_> = event
+            .args
+            .into_iter()
+            .map(|arg| JsHandle::new(Arc::clone(tab), arg))
+            .collect();
+        let text = args
+            .iter()
+            .map(JsHandle::description)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let location = event
+            .stack_trace
+            .as_ref()
+            .and_then(|stack| stack.call_frames.first())
+            .map(|frame| ConsoleMessageLocation {
+                url: frame.url.clone(),
+                line_number: Some(frame.line_number),
+                column_number: Some(frame.column_number),
+            });
+        Self {
+            level: console_api_level(&event.r#type),
+            text,
+            args,
+            location,
+            stack: event.stack_trace,
+        }
+    }
+
+    fn from_entry_added(tab: &Arc<PageInner>, event: EventEntryAdded) -> Self {
+        let entry = event.entry;
+        let line_number = entry.line_number;
+        let location = entry.url.map(|url| ConsoleMessageLocation {
+            url,
+            line_number,
+            column_number: None,
+        });
+        Self {
+            level: entry.level,
+            text: entry.text,
+            args: entry
+                .args
+                .unwrap_or_default()
+                .into_iter()
+                .map(|arg| JsHandle::new(Arc::clone(tab), arg))
+                .collect(),
+            location,
+            stack: entry.stack_trace,
+        }
+    }
+}
+
+/// The source location of a console message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsoleMessageLocation {
+    pub url: String,
+    /// 0-based line number in the resource
+    pub line_number: Option<i64>,
+    /// 0-based column number in the resource
+    pub column_number: Option<i64>,
+}
+
+/// An uncaught exception that was thrown in a page
+#[derive(Debug, Clone)]
+pub struct PageError {
+    /// The message of the exception, usually the `Error`'s description
+    /// including its stack
+    pub message: String,
+    /// Where the exception was thrown
+    pub location: Option<ConsoleMessageLocation>,
+    /// The javascript stack trace of the exception, if available
+    pub stack: Option<StackTrace>,
+    /// The full details of the exception as reported by chromium
+    pub details: ExceptionDetails,
+}
+
+impl PageError {
+    pub(crate) fn from_event(event: CdpEvent) -> Option<Self> {
+        if let CdpEvent::RuntimeExceptionThrown(ev) = event {
+            Some(Self::from(*ev))
+        } else {
+            None
+        }
+    }
+}
+
+impl From<EventExceptionThrown> for PageError {
+    fn from(event: EventExceptionThrown) -> Self {
+        let details = event.exception_details;
+        let message = details
+            .exception
+            .as_ref()
+            .and_then(|ex| ex.description.clone())
+            .unwrap_or_else(|| details.text.clone());
+        let location = details.url.clone().map(|url| ConsoleMessageLocation {
+            url,
+            line_number: Some(details.line_number),
+            column_number: Some(details.column_number),
+        });
+        Self {
+            message,
+            location,
+            stack: details.stack_trace.clone(),
+            details,
+        }
+    }
+}
+
+impl std::fmt::Display for PageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for PageError {}
+
+/// Maps the type of a `console` call to the level chromium displays it with
+fn console_api_level(kind: &ConsoleApiCalledType) -> LogEntryLevel {
+    match kind {
+        ConsoleApiCalledType::Error | ConsoleApiCalledType::Assert => LogEntryLevel::Error,
+        ConsoleApiCalledType::Warning => LogEntryLevel::Warning,
+        ConsoleApiCalledType::Debug => LogEntryLevel::Verbose,
+        _ => LogEntryLevel::Info,
+    }
+}
@@ -0,0 +1,128 @@
+use std::path::PathBuf;
+
+use serde_json::json;
+
+use crate::error::Result;
+
+/// Where the content of a `<script>` or `<style>` tag that is added to a page
+/// comes from.
+///
+/// # Example inject a library and a stylesheet
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::content::TagContent;
+/// # async fn demo(page: Page) -> Result<()> {
+///     page.add_script_tag(TagContent::url("https://unpkg.com/lodash")).await?;
+///     page.add_style_tag(TagContent::path("print.css")).await?;
+///     page.add_style_tag("body { margin: 0 }").await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagContent {
+    /// Load the script or stylesheet from the url
+    Url(String),
+    /// Read the content from the local file
+    Path(PathBuf),
+    /// Use the content as is
+    Inline(String),
+}
+
+impl TagContent {
+    pub fn url(url: impl Into<String>) -> Self {
+        TagContent::Url(url.into())
+    }
+
+    pub fn path(path: impl Into<PathBuf>) -> Self {
+        TagContent::Path(path.into())
+    }
+
+    pub fn inline(content: impl Into<String>) -> Self {
+        TagContent::Inline(content.into())
+    }
+
+    /// The expression that adds the tag and resolves with it, once it is
+    /// loaded if it refers to an url.
+    ///
+    /// Files are read and inlined with a `sourceURL` comment, so they show up
+    /// under their path in the devtools.
+    pub(crate) fn tag_expression(&self, tag: Tag) -> Result<String> {
+        let opts = match self {
+            TagContent::Url(url) => json!({ "url": url }),
+            TagContent::Path(path) => {
+                let mut content = std::fs::read_to_string(path)?;
+                let source_url = path.display().to_string().replace('\n', "");
+                match tag {
+                    Tag::Script => content.push_str(&format!("\n//# sourceURL={}", source_url)),
+                    Tag::Style => content.push_str(&format!("\n/*# sourceURL={}*/", source_url)),
+                }
+                json!({ "content": content })
+            }
+            TagContent::Inline(content) => json!({ "content": content }),
+        };
+        let function = match tag {
+            Tag::Script => ADD_SCRIPT_TAG,
+            Tag::Style => ADD_STYLE_TAG,
+        };
+        Ok(format!("({})({})", function, opts))
+    }
+}
+
+impl From<&str> for TagContent {
+    fn from(content: &str) -> Self {
+        TagContent::inline(content)
+    }
+}
+
+impl From<String> for TagContent {
+    fn from(content: String) -> Self {
+        TagContent::Inline(content)
+    }
+}
+
+/// The kind of tag to add
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Tag {
+    Script,
+    Style,
+}
+
+const ADD_SCRIPT_TAG: &str = r#"async ({ url, content }) => {
+    const script = document.createElement('script');
+    const parent = document.head || document.documentElement;
+    if (url) {
+        script.src = url;
+        const loaded = new Promise((resolve, reject) => {
+            script.onload = resolve;
+            script.onerror = () => reject(new Error(`Loading script from ${url} failed`));
+        });
+        parent.appendChild(script);
+        await loaded;
+    } else {
+        script.text = content;
+        parent.appendChild(script);
+    }
+    return script;
+}"#;
+
+const ADD_STYLE_TAG: &str = r#"async ({ url, content }) => {
+    const parent = document.head || document.documentElement;
+    if (url) {
+        const link = document.createElement('link');
+        link.rel = 'stylesheet';
+        link.href = url;
+        const loaded = new Promise((resolve, reject) => {
+            link.onload = resolve;
+            link.onerror = () => reject(new Error(`Loading stylesheet from ${url} failed`));
+        });
+        parent.appendChild(link);
+        await loaded;
+        return link;
+    }
+    const style = document.createElement('style');
+    style.appendChild(document.createTextNode(content));
+    parent.appendChild(style);
+    return style;
+}"#;
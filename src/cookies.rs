@@ -0,0 +1,84 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use chromiumoxide_cdp::cdp::browser_protocol::network::{Cookie, CookieParam, TimeSinceEpoch};
+
+use crate::error::Result;
+
+/// A collection of cookies that can be persisted as JSON, e.g. to restore a
+/// logged-in session in a later run.
+///
+/// # Example persist all cookies of the browser
+///
+/// ```no_run
+/// # use chromiumoxide::browser::Browser;
+/// # use chromiumoxide::cookies::CookieJar;
+/// # use chromiumoxide::error::Result;
+/// # async fn demo(browser: Browser) -> Result<()> {
+///     let jar = CookieJar::new(browser.get_all_cookies().await?);
+///     jar.save("cookies.json")?;
+///
+///     let jar = CookieJar::load("cookies.json")?;
+///     browser.set_cookies(jar.to_params()).await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CookieJar {
+    pub cookies: Vec<Cookie>,
+}
+
+impl CookieJar {
+    pub fn new(cookies: Vec<Cookie>) -> Self {
+        Self { cookies }
+    }
+
+    /// Parses a jar previously serialized with `CookieJar::to_json`
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serializes the jar as JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads the jar from the JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Writes the jar as JSON to the file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// The cookies as parameters to set them again, session cookies are not
+    /// given an expiration date.
+    pub fn to_params(&self) -> Vec<CookieParam> {
+        self.cookies.iter().map(cookie_param).collect()
+    }
+}
+
+impl From<Vec<Cookie>> for CookieJar {
+    fn from(cookies: Vec<Cookie>) -> Self {
+        Self::new(cookies)
+    }
+}
+
+/// Converts a cookie as returned by chromium to the parameter to set it
+pub fn cookie_param(cookie: &Cookie) -> CookieParam {
+    let mut param = CookieParam::new(cookie.name.clone(), cookie.value.clone());
+    param.domain = Some(cookie.domain.clone());
+    param.path = Some(cookie.path.clone());
+    param.secure = Some(cookie.secure);
+    param.http_only = Some(cookie.http_only);
+    param.same_site = cookie.same_site.clone();
+    param.priority = Some(cookie.priority.clone());
+    if !cookie.session {
+        param.expires = serde_json::from_value::<TimeSinceEpoch>(cookie.expires.into()).ok();
+    }
+    param
+}
@@ -0,0 +1,718 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::ops::Range;
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::{FutureExt, StreamExt};
+use serde_json::json;
+
+use chromiumoxide_cdp::cdp::browser_protocol::css::{
+    self, EventStyleSheetAdded, GetStyleSheetTextParams, StartRuleUsageTrackingParams,
+    StopRuleUsageTrackingParams,
+};
+use chromiumoxide_cdp::cdp::browser_protocol::dom;
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+use chromiumoxide_cdp::cdp::js_protocol::debugger::{
+    self, EventScriptParsed, GetScriptSourceParams,
+};
+use chromiumoxide_cdp::cdp::js_protocol::profiler::{
+    self, StartPreciseCoverageParams, StopPreciseCoverageParams, TakePreciseCoverageParams,
+};
+use chromiumoxide_cdp::cdp::js_protocol::runtime::EventExecutionContextsCleared;
+
+use crate::error::{CdpError, Result};
+use crate::handler::PageInner;
+
+/// Collects which parts of the JavaScript and CSS of a page are used.
+///
+/// All `Coverage` handles of a page share their state, so coverage that was
+/// started with one handle can be stopped with another.
+///
+/// # Example export the JavaScript coverage of a test run as LCOV
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::coverage::{self, JsCoverageOptions};
+/// # async fn demo(page: Page) -> Result<()> {
+///     let coverage = page.coverage();
+///     coverage.start_js(JsCoverageOptions::default()).await?;
+///     page.goto("https://example.com").await?;
+///     let entries = coverage.stop_js().await?;
+///     std::fs::write("e2e.lcov", coverage::to_lcov(&entries))?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    tab: Arc<PageInner>,
+}
+
+/// The coverage that is currently collected for a page
+#[derive(Debug, Default)]
+pub(crate) struct CoverageState {
+    /// The `Debugger.scriptParsed` events while JavaScript coverage is
+    /// collected
+    js: Mutex<Option<(JsCoverageOptions, UnboundedReceiver<CdpEvent>)>>,
+    /// The `CSS.styleSheetAdded` events while CSS coverage is collected, and
+    /// whether to reset on navigation
+    css: Mutex<Option<(bool, UnboundedReceiver<CdpEvent>)>>,
+}
+
+impl Coverage {
+    pub(crate) fn new(tab: Arc<PageInner>) -> Self {
+        Self { tab }
+    }
+
+    fn state(&self) -> &CoverageState {
+        self.tab.coverage_state()
+    }
+
+    /// Starts collecting which JavaScript code is executed.
+    ///
+    /// This enables the `Profiler` and `Debugger` domains, which are needed
+    /// to collect the coverage and the sources of the scripts.
+    pub async fn start_js(&self, opts: JsCoverageOptions) -> Result<&Self> {
+        if self.state().js.lock().unwrap().is_some() {
+            return Err(CdpError::msg("JavaScript coverage is already enabled"));
+        }
+        let events = self
+            .tab
+            .event_listener(vec![
+                EventScriptParsed::IDENTIFIER,
+                EventExecutionContextsCleared::IDENTIFIER,
+            ])
+            .await?;
+        self.tab.execute(profiler::EnableParams::default()).await?;
+        self.tab
+            .execute(
+                StartPreciseCoverageParams::builder()
+                    .call_count(true)
+                    .detailed(opts.detailed)
+                    .build(),
+            )
+            .await?;
+        self.tab.execute(debugger::EnableParams::default()).await?;
+        *self.state().js.lock().unwrap() = Some((opts, events));
+        Ok(self)
+    }
+
+    /// Stops collecting JavaScript coverage and returns the coverage of all
+    /// scripts that were parsed in the meantime.
+    pub async fn stop_js(&self) -> Result<Vec<CoverageEntry>> {
+        let (opts, mut events) = self
+            .state()
+            .js
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| CdpError::msg("JavaScript coverage is not enabled"))?;
+        let coverage = self
+            .tab
+            .execute(TakePreciseCoverageParams::default())
+            .await?
+            .result
+            .result;
+
+        // all scripts were reported before the response to the coverage
+        let mut scripts = HashMap::new();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            match event {
+                CdpEvent::DebuggerScriptParsed(ev)
+                    if !ev.url.is_empty() || opts.report_anonymous_scripts =>
+                {
+                    scripts.insert(ev.script_id.clone(), ev.url.clone());
+                }
+                CdpEvent::RuntimeExecutionContextsCleared(_) if opts.reset_on_navigation => {
+                    scripts.clear()
+                }
+                _ => {}
+            }
+        }
+
+        let mut entries = Vec::new();
+        for script in coverage {
+            let url = match scripts.get(&script.script_id) {
+                Some(url) => url.clone(),
+                None => continue,
+            };
+            // scripts may have been collected in the meantime
+            let text = match self
+                .tab
+                .execute(GetScriptSourceParams::new(script.script_id))
+                .await
+            {
+                Ok(resp) => resp.result.script_source,
+                Err(_) => continue,
+            };
+            let offsets = Utf16Offsets::new(&text);
+            let mut blocks = Vec::new();
+            let mut functions = Vec::new();
+            for function in script.functions {
+                if let Some(range) = function.ranges.first() {
+                    functions.push(FunctionRange {
+                        name: function.function_name.clone(),
+                        range: offsets.range(range.start_offset, range.end_offset),
+                        count: range.count,
+                    });
+                }
+                blocks.extend(function.ranges.iter().map(|range| CountedRange {
+                    range: offsets.range(range.start_offset, range.end_offset),
+                    count: range.count,
+                }));
+            }
+            entries.push(CoverageEntry::new(url, text, blocks, functions));
+        }
+
+        self.tab
+            .execute(StopPreciseCoverageParams::default())
+            .await?;
+        Ok(entries)
+    }
+
+    /// Starts collecting which CSS rules are used.
+    ///
+    /// With `reset_on_navigation` only stylesheets of the document that is
+    /// shown when coverage is stopped are reported.
+    ///
+    /// This enables the `DOM` and `CSS` domains, which stay enabled after
+    /// coverage is stopped.
+    pub async fn start_css(&self, reset_on_navigation: bool) -> Result<&Self> {
+        if self.state().css.lock().unwrap().is_some() {
+            return Err(CdpError::msg("CSS coverage is already enabled"));
+        }
+        let events = self
+            .tab
+            .event_listener(vec![
+                EventStyleSheetAdded::IDENTIFIER,
+                EventExecutionContextsCleared::IDENTIFIER,
+            ])
+            .await?;
+        self.tab.execute(dom::EnableParams::default()).await?;
+        self.tab.execute(css::EnableParams::default()).await?;
+        self.tab
+            .execute(StartRuleUsageTrackingParams::default())
+            .await?;
+        *self.state().css.lock().unwrap() = Some((reset_on_navigation, events));
+        Ok(self)
+    }
+
+    /// Stops collecting CSS coverage and returns the coverage of all
+    /// stylesheets that were loaded from an url in the meantime.
+    pub async fn stop_css(&self) -> Result<Vec<CoverageEntry>> {
+        let (reset_on_navigation, mut events) = self
+            .state()
+            .css
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| CdpError::msg("CSS coverage is not enabled"))?;
+        let usage = self
+            .tab
+            .execute(StopRuleUsageTrackingParams::default())
+            .await?
+            .result
+            .rule_usage;
+
+        let mut sheets = Vec::new();
+        while let Some(Some(event)) = events.next().now_or_never() {
+            match event {
+                // inline stylesheets have no url
+                CdpEvent::CssStyleSheetAdded(ev) if !ev.header.source_url.is_empty() => {
+                    sheets.push((ev.header.style_sheet_id, ev.header.source_url));
+                }
+                CdpEvent::RuntimeExecutionContextsCleared(_) if reset_on_navigation => {
+                    sheets.clear()
+                }
+                _ => {}
+            }
+        }
+
+        let mut entries = Vec::new();
+        for (id, url) in sheets {
+            let text = match self
+                .tab
+                .execute(GetStyleSheetTextParams::new(id.clone()))
+                .await
+            {
+                Ok(resp) => resp.result.text,
+                Err(_) => continue,
+            };
+            let offsets = Utf16Offsets::new(&text);
+            let blocks = usage
+                .iter()
+                .filter(|rule| rule.style_sheet_id == id)
+                .map(|rule| CountedRange {
+                    range: offsets.range(rule.start_offset as i64, rule.end_offset as i64),
+                    count: rule.used as i64,
+                })
+                .collect();
+            entries.push(CoverageEntry::new(url, text, blocks, Vec::new()));
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Configures how JavaScript coverage is collected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsCoverageOptions {
+    /// Only report scripts of the document that is shown when coverage is
+    /// stopped, defaults to true
+    pub reset_on_navigation: bool,
+    /// Also report scripts without url, like those created via `eval`,
+    /// defaults to false
+    pub report_anonymous_scripts: bool,
+    /// Collect block level coverage instead of only function level coverage,
+    /// defaults to true
+    pub detailed: bool,
+}
+
+impl JsCoverageOptions {
+    pub fn reset_on_navigation(mut self, reset_on_navigation: bool) -> Self {
+        self.reset_on_navigation = reset_on_navigation;
+        self
+    }
+
+    pub fn report_anonymous_scripts(mut self, report_anonymous_scripts: bool) -> Self {
+        self.report_anonymous_scripts = report_anonymous_scripts;
+        self
+    }
+
+    pub fn detailed(mut self, detailed: bool) -> Self {
+        self.detailed = detailed;
+        self
+    }
+}
+
+impl Default for JsCoverageOptions {
+    fn default() -> Self {
+        Self {
+            reset_on_navigation: true,
+            report_anonymous_scripts: false,
+            detailed: true,
+        }
+    }
+}
+
+/// A range of the source that was executed `count` times, or for CSS rules
+/// once if the rule was used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountedRange {
+    /// Byte range in the source
+    pub range: Range<usize>,
+    pub count: i64,
+}
+
+/// A JavaScript function and how often it was called
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionRange {
+    /// The name of the function, empty for anonymous functions and the
+    /// top-level code of the script
+    pub name: String,
+    /// Byte range of the function in the source
+    pub range: Range<usize>,
+    pub count: i64,
+}
+
+/// The coverage of a single script or stylesheet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageEntry {
+    pub url: String,
+    /// The source of the script or stylesheet
+    pub text: String,
+    /// The sorted, disjoint byte ranges of `text` that were used
+    pub ranges: Vec<Range<usize>>,
+    /// The sorted, disjoint byte ranges of `text` with how often they were
+    /// used, by the innermost block that contains them
+    pub blocks: Vec<CountedRange>,
+    /// The functions of a script, empty for stylesheets
+    pub functions: Vec<FunctionRange>,
+}
+
+impl CoverageEntry {
+    fn new(
+        url: String,
+        text: String,
+        blocks: Vec<CountedRange>,
+        functions: Vec<FunctionRange>,
+    ) -> Self {
+        let blocks = disjoint_ranges(blocks);
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        for block in blocks.iter().filter(|block| block.count > 0) {
+            match ranges.last_mut() {
+                Some(last) if last.end == block.range.start => last.end = block.range.end,
+                _ => ranges.push(block.range.clone()),
+            }
+        }
+        Self {
+            url,
+            text,
+            ranges,
+            blocks,
+            functions,
+        }
+    }
+
+    /// How often the block containing the byte offset was used, if any
+    fn count_at(&self, offset: usize) -> Option<i64> {
+        let idx = self
+            .blocks
+            .partition_point(|block| block.range.end <= offset);
+        self.blocks
+            .get(idx)
+            .filter(|block| block.range.contains(&offset))
+            .map(|block| block.count)
+    }
+
+    /// The 1-based line numbers with how often the first non-whitespace
+    /// character of the line was used, skipping blank lines and lines outside
+    /// of any block.
+    pub fn line_counts(&self) -> Vec<(usize, i64)> {
+        let mut counts = Vec::new();
+        let mut start = 0;
+        for (idx, line) in self.text.split('\n').enumerate() {
+            if let Some(indent) = line.find(|c: char| !c.is_whitespace()) {
+                if let Some(count) = self.count_at(start + indent) {
+                    counts.push((idx + 1, count));
+                }
+            }
+            start += line.len() + 1;
+        }
+        counts
+    }
+
+    /// 1-based line and 0-based column of the byte offset
+    fn position(&self, offset: usize) -> (usize, usize) {
+        let before = &self.text[..offset.min(self.text.len())];
+        let line_start = before.rfind('\n').map(|idx| idx + 1).unwrap_or_default();
+        (
+            before.matches('\n').count() + 1,
+            before[line_start..].chars().count(),
+        )
+    }
+
+    /// Name of the function in reports, anonymous functions are numbered
+    fn function_name(idx: usize, function: &FunctionRange) -> String {
+        if function.name.is_empty() {
+            format!("(anonymous_{})", idx)
+        } else {
+            function.name.clone()
+        }
+    }
+}
+
+/// Exports the coverage in the LCOV tracefile format, as used by `genhtml`
+/// and most coverage services.
+pub fn to_lcov(entries: &[CoverageEntry]) -> String {
+    let mut lcov = String::new();
+    for entry in entries {
+        let _ = writeln!(lcov, "TN:");
+        let _ = writeln!(lcov, "SF:{}", entry.url);
+        for (idx, function) in entry.functions.iter().enumerate() {
+            let (line, _) = entry.position(function.range.start);
+            let name = CoverageEntry::function_name(idx, function);
+            let _ = writeln!(lcov, "FN:{},{}", line, name);
+        }
+        for (idx, function) in entry.functions.iter().enumerate() {
+            let name = CoverageEntry::function_name(idx, function);
+            let _ = writeln!(lcov, "FNDA:{},{}", function.count, name);
+        }
+        let _ = writeln!(lcov, "FNF:{}", entry.functions.len());
+        let _ = writeln!(
+            lcov,
+            "FNH:{}",
+            entry.functions.iter().filter(|f| f.count > 0).count()
+        );
+        let lines = entry.line_counts();
+        for (line, count) in &lines {
+            let _ = writeln!(lcov, "DA:{},{}", line, count);
+        }
+        let _ = writeln!(lcov, "LF:{}", lines.len());
+        let _ = writeln!(
+            lcov,
+            "LH:{}",
+            lines.iter().filter(|(_, count)| *count > 0).count()
+        );
+        let _ = writeln!(lcov, "end_of_record");
+    }
+    lcov
+}
+
+/// Exports the coverage as Istanbul `coverage-final.json` object, keyed by
+/// url, with a statement per line, to be merged with the coverage of unit
+/// tests via `nyc merge` or `istanbul-lib-coverage`.
+pub fn to_istanbul(entries: &[CoverageEntry]) -> serde_json::Value {
+    let location = |entry: &CoverageEntry, range: &Range<usize>| {
+        let (start_line, start_column) = entry.position(range.start);
+        let (end_line, end_column) = entry.position(range.end);
+        json!({
+            "start": { "line": start_line, "column": start_column },
+            "end": { "line": end_line, "column": end_column },
+        })
+    };
+
+    let mut files = serde_json::Map::new();
+    for entry in entries {
+        let mut statement_map = serde_json::Map::new();
+        let mut statements = serde_json::Map::new();
+        let lines: Vec<&str> = entry.text.split('\n').collect();
+        for (idx, (line, count)) in entry.line_counts().into_iter().enumerate() {
+            let columns = lines[line - 1].chars().count();
+            statement_map.insert(
+                idx.to_string(),
+                json!({
+                    "start": { "line": line, "column": 0 },
+                    "end": { "line": line, "column": columns },
+                }),
+            );
+            statements.insert(idx.to_string(), json!(count));
+        }
+
+        let mut fn_map = serde_json::Map::new();
+        let mut functions = serde_json::Map::new();
+        for (idx, function) in entry.functions.iter().enumerate() {
+            let loc = location(entry, &function.range);
+            fn_map.insert(
+                idx.to_string(),
+                json!({
+                    "name": CoverageEntry::function_name(idx, function),
+                    "decl": loc,
+                    "loc": loc,
+                    "line": loc["start"]["line"],
+                }),
+            );
+            functions.insert(idx.to_string(), json!(function.count));
+        }
+
+        files.insert(
+            entry.url.clone(),
+            json!({
+                "path": entry.url,
+                "statementMap": statement_map,
+                "fnMap": fn_map,
+                "branchMap": {},
+                "s": statements,
+                "f": functions,
+                "b": {},
+            }),
+        );
+    }
+    serde_json::Value::Object(files)
+}
+
+/// Converts nested ranges into sorted, disjoint ranges, where each part has
+/// the count of the innermost range that contains it.
+fn disjoint_ranges(ranges: Vec<CountedRange>) -> Vec<CountedRange> {
+    // (offset, is_start, range)
+    let mut points: Vec<(usize, bool, &CountedRange)> = ranges
+        .iter()
+        .flat_map(|range| {
+            vec![
+                (range.range.start, true, range),
+                (range.range.end, false, range),
+            ]
+        })
+        .collect();
+    points.sort_by(|(a_offset, a_start, a), (b_offset, b_start, b)| {
+        a_offset
+            .cmp(b_offset)
+            // ranges end before others start at the same offset
+            .then_with(|| a_start.cmp(b_start))
+            .then_with(|| {
+                let a_len = a.range.end - a.range.start;
+                let b_len = b.range.end - b.range.start;
+                if *a_start {
+                    // outer ranges start first
+                    b_len.cmp(&a_len)
+                } else {
+                    // inner ranges end first
+                    a_len.cmp(&b_len)
+                }
+            })
+    });
+
+    let mut counts: Vec<i64> = Vec::new();
+    let mut disjoint: Vec<CountedRange> = Vec::new();
+    let mut last_offset = 0;
+    for (offset, is_start, range) in points {
+        if let Some(&count) = counts.last() {
+            if last_offset < offset {
+                match disjoint.last_mut() {
+                    Some(last) if last.range.end == last_offset && last.count == count => {
+                        last.range.end = offset
+                    }
+                    _ => disjoint.push(CountedRange {
+                        range: last_offset..offset,
+                        count,
+                    }),
+                }
+            }
+        }
+        last_offset = offset;
+        if is_start {
+            counts.push(range.count);
+        } else {
+            counts.pop();
+        }
+    }
+    disjoint
+}
+
+/// Maps the UTF-16 offsets reported by chromium to byte offsets of the text
+struct Utf16Offsets {
+    /// The byte offset of each UTF-16 code unit
+    bytes: Vec<usize>,
+    len: usize,
+}
+
+impl Utf16Offsets {
+    fn new(text: &str) -> Self {
+        let mut bytes = Vec::with_capacity(text.len());
+        for (idx, c) in text.char_indices() {
+            bytes.extend(std::iter::repeat_n(idx, c.len_utf16()));
+        }
+        Self {
+            bytes,
+            len: text.len(),
+        }
+    }
+
+    fn offset(&self, offset: i64) -> usize {
+        self.bytes
+            .get(offset.max(0) as usize)
+            .copied()
+            .unwrap_or(self.len)
+    }
+
+    fn range(&self, start: i64, end: i64) -> Range<usize> {
+        self.offset(start)..self.offset(end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counted(range: Range<usize>, count: i64) -> CountedRange {
+        CountedRange { range, count }
+    }
+
+    const SCRIPT: &str = "function f() {\n  return 1;\n}\n\nfunction g() {\n  return 2;\n}\n";
+
+    /// `f` was called once, `g` never
+    fn script_entry() -> CoverageEntry {
+        CoverageEntry::new(
+            "https://example.com/app.js".to_string(),
+            SCRIPT.to_string(),
+            vec![counted(0..59, 1), counted(0..29, 1), counted(30..59, 0)],
+            vec![
+                FunctionRange {
+                    name: "f".to_string(),
+                    range: 0..29,
+                    count: 1,
+                },
+                FunctionRange {
+                    name: String::new(),
+                    range: 30..59,
+                    count: 0,
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn disjoint_nested_ranges() {
+        let ranges = disjoint_ranges(vec![counted(0..10, 1), counted(2..5, 0)]);
+        assert_eq!(
+            ranges,
+            vec![counted(0..2, 1), counted(2..5, 0), counted(5..10, 1)]
+        );
+    }
+
+    #[test]
+    fn disjoint_adjacent_ranges() {
+        // adjacent parts with the same count are merged
+        let ranges = disjoint_ranges(vec![counted(0..10, 1), counted(0..4, 2), counted(4..8, 2)]);
+        assert_eq!(ranges, vec![counted(0..8, 2), counted(8..10, 1)]);
+
+        let ranges = disjoint_ranges(vec![counted(0..4, 1), counted(4..8, 0)]);
+        assert_eq!(ranges, vec![counted(0..4, 1), counted(4..8, 0)]);
+    }
+
+    #[test]
+    fn utf16_offsets_of_non_ascii_text() {
+        // `é` is 2 bytes and 1 code unit, `😀` 4 bytes and 2 code units
+        let offsets = Utf16Offsets::new("aé😀b");
+        assert_eq!(offsets.offset(0), 0);
+        assert_eq!(offsets.offset(1), 1);
+        assert_eq!(offsets.offset(2), 3);
+        assert_eq!(offsets.offset(4), 7);
+        assert_eq!(offsets.offset(5), 8);
+        assert_eq!(offsets.offset(-1), 0);
+        assert_eq!(offsets.offset(100), 8);
+        assert_eq!(offsets.range(2, 5), 3..8);
+    }
+
+    #[test]
+    fn used_ranges_and_line_counts() {
+        let entry = script_entry();
+        assert_eq!(entry.ranges, vec![0..30]);
+        assert_eq!(
+            entry.line_counts(),
+            vec![(1, 1), (2, 1), (3, 1), (5, 0), (6, 0), (7, 0)]
+        );
+    }
+
+    #[test]
+    fn lcov_tracefile() {
+        let expected = "TN:
+SF:https://example.com/app.js
+FN:1,f
+FN:5,(anonymous_1)
+FNDA:1,f
+FNDA:0,(anonymous_1)
+FNF:2
+FNH:1
+DA:1,1
+DA:2,1
+DA:3,1
+DA:5,0
+DA:6,0
+DA:7,0
+LF:6
+LH:3
+end_of_record
+";
+        assert_eq!(to_lcov(&[script_entry()]), expected);
+    }
+
+    #[test]
+    fn istanbul_statements_and_functions() {
+        let istanbul = to_istanbul(&[script_entry()]);
+        let file = &istanbul["https://example.com/app.js"];
+        assert_eq!(file["path"], "https://example.com/app.js");
+        assert_eq!(
+            file["s"],
+            json!({ "0": 1, "1": 1, "2": 1, "3": 0, "4": 0, "5": 0 })
+        );
+        assert_eq!(
+            file["statementMap"]["1"],
+            json!({
+                "start": { "line": 2, "column": 0 },
+                "end": { "line": 2, "column": 11 },
+            })
+        );
+        assert_eq!(file["f"], json!({ "0": 1, "1": 0 }));
+        assert_eq!(file["fnMap"]["1"]["name"], "(anonymous_1)");
+        assert_eq!(file["fnMap"]["1"]["line"], 5);
+        assert_eq!(
+            file["fnMap"]["1"]["loc"],
+            json!({
+                "start": { "line": 5, "column": 0 },
+                "end": { "line": 8, "column": 0 },
+            })
+        );
+    }
+}
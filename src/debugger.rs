@@ -0,0 +1,446 @@
+use std::sync::{Arc, Mutex};
+
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::{future, FutureExt, Stream, StreamExt};
+
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+use chromiumoxide_cdp::cdp::js_protocol::debugger::{
+    self, BreakpointId, CallFrame, CallFrameId, EvaluateOnCallFrameParams, EventPaused,
+    EventScriptParsed, GetScriptSourceParams, Location, PauseParams, PausedReason,
+    RemoveBreakpointParams, ResumeParams, SetBreakpointByUrlParams, SetPauseOnExceptionsParams,
+    SetPauseOnExceptionsState, StepIntoParams, StepOutParams, StepOverParams,
+};
+use chromiumoxide_cdp::cdp::js_protocol::runtime::{
+    EventExecutionContextsCleared, ExecutionContextId, GetPropertiesParams, PropertyDescriptor,
+    RemoteObject, ScriptId,
+};
+
+use crate::error::{CdpError, Result};
+use crate::handler::PageInner;
+
+/// Controls the JavaScript debugger of a page.
+///
+/// The debugger tracks the scripts that are parsed while it is enabled, sets
+/// breakpoints and reports whenever the execution is paused. While paused,
+/// expressions can be evaluated on the call frames before the execution is
+/// resumed or stepped.
+///
+/// Line and column numbers are 0-based, like in the `Debugger` domain. All
+/// `Debugger` handles of a page share the tracked scripts.
+///
+/// # Example trace the local variables at a breakpoint
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use futures::StreamExt;
+/// # async fn demo(page: Page) -> Result<()> {
+///     let debugger = page.debugger();
+///     let mut paused = debugger.paused().await?;
+///     debugger.enable().await?;
+///     debugger.set_breakpoint_by_url("https://example.com/app.js", 41).await?;
+///     page.goto("https://example.com").await?;
+///
+///     while let Some(pause) = paused.next().await {
+///         let frame = &pause.call_frames[0];
+///         for scope in &frame.scope_chain {
+///             for var in debugger.scope_variables(scope).await? {
+///                 println!("{:?} {} = {:?}", scope.r#type, var.name, var.value);
+///             }
+///         }
+///         let total = debugger.evaluate_on_call_frame(&frame.call_frame_id, "total").await?;
+///         println!("total = {:?}", total.value);
+///         debugger.step_over().await?;
+///     }
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    tab: Arc<PageInner>,
+}
+
+/// The scripts of a page that the debugger tracks
+#[derive(Debug, Default)]
+pub(crate) struct DebuggerState {
+    /// The `Debugger.scriptParsed` events while the debugger is enabled
+    events: Mutex<Option<UnboundedReceiver<CdpEvent>>>,
+    /// All scripts of the page that were parsed so far
+    scripts: Mutex<Vec<Script>>,
+}
+
+impl Debugger {
+    pub(crate) fn new(tab: Arc<PageInner>) -> Self {
+        Self { tab }
+    }
+
+    fn state(&self) -> &DebuggerState {
+        self.tab.debugger_state()
+    }
+
+    /// Enables the debugger.
+    ///
+    /// All scripts that were already parsed are reported right away.
+    pub async fn enable(&self) -> Result<&Self> {
+        if self.state().events.lock().unwrap().is_some() {
+            return Ok(self);
+        }
+        let events = self
+            .tab
+            .event_listener(vec![
+                EventScriptParsed::IDENTIFIER,
+                EventExecutionContextsCleared::IDENTIFIER,
+            ])
+            .await?;
+        self.tab.execute(debugger::EnableParams::default()).await?;
+        *self.state().events.lock().unwrap() = Some(events);
+        Ok(self)
+    }
+
+    /// Disables the debugger, which also removes all breakpoints and resumes
+    /// the execution if paused
+    pub async fn disable(&self) -> Result<&Self> {
+        self.tab.execute(debugger::DisableParams::default()).await?;
+        self.state().events.lock().unwrap().take();
+        self.state().scripts.lock().unwrap().clear();
+        Ok(self)
+    }
+
+    /// All scripts of the page that were parsed since the debugger was
+    /// enabled, in the order they were parsed.
+    ///
+    /// Scripts of execution contexts that were destroyed, for example by a
+    /// navigation, are removed.
+    pub fn scripts(&self) -> Vec<Script> {
+        let state = self.state();
+        let mut scripts = state.scripts.lock().unwrap();
+        if let Some(events) = state.events.lock().unwrap().as_mut() {
+            while let Some(Some(event)) = events.next().now_or_never() {
+                match event {
+                    CdpEvent::DebuggerScriptParsed(ev) => scripts.push((*ev).into()),
+                    CdpEvent::RuntimeExecutionContextsCleared(_) => scripts.clear(),
+                    _ => {}
+                }
+            }
+        }
+        scripts.clone()
+    }
+
+    /// The scripts that were loaded from the `url`
+    pub fn scripts_by_url(&self, url: &str) -> Vec<Script> {
+        self.scripts()
+            .into_iter()
+            .filter(|script| script.url == url)
+            .collect()
+    }
+
+    /// Returns the source of the script
+    pub async fn script_source(&self, script_id: ScriptId) -> Result<String> {
+        Ok(self
+            .tab
+            .execute(GetScriptSourceParams::new(script_id))
+            .await?
+            .result
+            .script_source)
+    }
+
+    /// Sets a breakpoint at the line of all scripts with the `url`, including
+    /// scripts that are parsed later on.
+    pub async fn set_breakpoint_by_url(
+        &self,
+        url: impl Into<String>,
+        line_number: i64,
+    ) -> Result<Breakpoint> {
+        self.set_breakpoint(
+            SetBreakpointByUrlParams::builder()
+                .url(url)
+                .line_number(line_number)
+                .build()
+                .map_err(CdpError::msg)?,
+        )
+        .await
+    }
+
+    /// Sets a breakpoint at the line of all scripts whose url matches the
+    /// regular expression, including scripts that are parsed later on.
+    pub async fn set_breakpoint_by_url_regex(
+        &self,
+        url_regex: impl Into<String>,
+        line_number: i64,
+    ) -> Result<Breakpoint> {
+        self.set_breakpoint(
+            SetBreakpointByUrlParams::builder()
+                .url_regex(url_regex)
+                .line_number(line_number)
+                .build()
+                .map_err(CdpError::msg)?,
+        )
+        .await
+    }
+
+    /// Sets a breakpoint, for example with a column or a condition
+    pub async fn set_breakpoint(&self, params: SetBreakpointByUrlParams) -> Result<Breakpoint> {
+        let resp = self.tab.execute(params).await?.result;
+        Ok(Breakpoint {
+            id: resp.breakpoint_id,
+            locations: resp.locations,
+        })
+    }
+
+    /// Removes the breakpoint
+    pub async fn remove_breakpoint(&self, breakpoint_id: BreakpointId) -> Result<&Self> {
+        self.tab
+            .execute(RemoveBreakpointParams::new(breakpoint_id))
+            .await?;
+        Ok(self)
+    }
+
+    /// Whether to pause on no, uncaught or all exceptions
+    pub async fn set_pause_on_exceptions(&self, state: SetPauseOnExceptionsState) -> Result<&Self> {
+        self.tab
+            .execute(SetPauseOnExceptionsParams::new(state))
+            .await?;
+        Ok(self)
+    }
+
+    /// Returns a stream of all the times the execution was paused.
+    ///
+    /// The execution stays paused until it is resumed or stepped.
+    pub async fn paused(&self) -> Result<impl Stream<Item = Paused>> {
+        let events = self
+            .tab
+            .event_listener(vec![EventPaused::IDENTIFIER])
+            .await?;
+        Ok(events.filter_map(|ev| future::ready(Paused::from_event(ev))))
+    }
+
+    /// Pauses the execution on the next JavaScript statement
+    pub async fn pause(&self) -> Result<&Self> {
+        self.tab.execute(PauseParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Resumes the paused execution
+    pub async fn resume(&self) -> Result<&Self> {
+        self.tab.execute(ResumeParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Steps over the next statement
+    pub async fn step_over(&self) -> Result<&Self> {
+        self.tab.execute(StepOverParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Steps into the function call of the next statement
+    pub async fn step_into(&self) -> Result<&Self> {
+        self.tab.execute(StepIntoParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Steps out of the current function
+    pub async fn step_out(&self) -> Result<&Self> {
+        self.tab.execute(StepOutParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Evaluates the expression in the scope of the paused call frame.
+    ///
+    /// The result is returned by value, values that are not serializable,
+    /// like DOM nodes, have no `value`.
+    pub async fn evaluate_on_call_frame(
+        &self,
+        call_frame_id: &CallFrameId,
+        expression: impl Into<String>,
+    ) -> Result<RemoteObject> {
+        let resp = self
+            .tab
+            .execute(
+                EvaluateOnCallFrameParams::builder()
+                    .call_frame_id(call_frame_id.clone())
+                    .expression(expression)
+                    .return_by_value(true)
+                    .build()
+                    .map_err(CdpError::msg)?,
+            )
+            .await?
+            .result;
+        if let Some(details) = resp.exception_details {
+            return Err(CdpError::msg(
+                details
+                    .exception
+                    .and_then(|err| err.description)
+                    .unwrap_or(details.text),
+            ));
+        }
+        Ok(resp.result)
+    }
+
+    /// The variables of a scope of a paused call frame
+    pub async fn scope_variables(
+        &self,
+        scope: &debugger::Scope,
+    ) -> Result<Vec<PropertyDescriptor>> {
+        let object_id = scope
+            .object
+            .object_id
+            .clone()
+            .ok_or_else(|| CdpError::msg("Scope has no object"))?;
+        Ok(self
+            .tab
+            .execute(
+                GetPropertiesParams::builder()
+                    .object_id(object_id)
+                    .own_properties(true)
+                    .build()
+                    .map_err(CdpError::msg)?,
+            )
+            .await?
+            .result
+            .result)
+    }
+}
+
+/// A script that was parsed by the page
+#[derive(Debug, Clone, PartialEq)]
+pub struct Script {
+    pub script_id: ScriptId,
+    /// The url the script was loaded from, or its `sourceURL`, empty for
+    /// inline and anonymous scripts
+    pub url: String,
+    /// The url of the source map of the script
+    pub source_map_url: Option<String>,
+    /// The execution context the script was parsed in
+    pub execution_context_id: ExecutionContextId,
+    pub start_line: i64,
+    pub start_column: i64,
+    pub end_line: i64,
+    pub end_column: i64,
+    /// The hash of the content of the script
+    pub hash: String,
+    pub is_module: bool,
+}
+
+impl From<EventScriptParsed> for Script {
+    fn from(ev: EventScriptParsed) -> Self {
+        Self {
+            script_id: ev.script_id,
+            url: ev.url,
+            source_map_url: ev.source_map_url.filter(|url| !url.is_empty()),
+            execution_context_id: ev.execution_context_id,
+            start_line: ev.start_line,
+            start_column: ev.start_column,
+            end_line: ev.end_line,
+            end_column: ev.end_column,
+            hash: ev.hash,
+            is_module: ev.is_module.unwrap_or_default(),
+        }
+    }
+}
+
+/// A breakpoint that was set
+#[derive(Debug, Clone, PartialEq)]
+pub struct Breakpoint {
+    pub id: BreakpointId,
+    /// The locations the breakpoint resolved to in the scripts that were
+    /// already parsed
+    pub locations: Vec<Location>,
+}
+
+/// The execution of the page was paused
+#[derive(Debug, Clone, PartialEq)]
+pub struct Paused {
+    pub reason: PausedReason,
+    /// The call stack, the innermost frame first. Each frame includes its
+    /// scope chain.
+    pub call_frames: Vec<CallFrame>,
+    /// The ids of the breakpoints that were hit
+    pub hit_breakpoints: Vec<BreakpointId>,
+    /// Additional data depending on the `reason`, like the exception
+    pub data: Option<serde_json::Value>,
+}
+
+impl Paused {
+    pub(crate) fn from_event(event: CdpEvent) -> Option<Self> {
+        if let CdpEvent::DebuggerPaused(ev) = event {
+            Some(Self {
+                reason: ev.reason,
+                call_frames: ev.call_frames,
+                hit_breakpoints: ev
+                    .hit_breakpoints
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(BreakpointId::from)
+                    .collect(),
+                data: ev.data,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The innermost call frame, where the execution is paused
+    pub fn top_frame(&self) -> Option<&CallFrame> {
+        self.call_frames.first()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script_parsed(source_map_url: &str) -> EventScriptParsed {
+        serde_json::from_value(serde_json::json!({
+            "scriptId": "42",
+            "url": "https://example.com/app.js",
+            "startLine": 0,
+            "startColumn": 0,
+            "endLine": 10,
+            "endColumn": 1,
+            "executionContextId": 1,
+            "hash": "abc",
+            "sourceMapURL": source_map_url,
+            "isModule": true
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn script_from_parsed_event() {
+        let script = Script::from(script_parsed("app.js.map"));
+        assert_eq!(script.script_id, ScriptId::from("42".to_string()));
+        assert_eq!(script.url, "https://example.com/app.js");
+        assert_eq!(script.source_map_url.as_deref(), Some("app.js.map"));
+        assert_eq!(script.end_line, 10);
+        assert!(script.is_module);
+    }
+
+    #[test]
+    fn script_without_source_map() {
+        // chromium reports a missing source map as empty url
+        assert_eq!(Script::from(script_parsed("")).source_map_url, None);
+    }
+
+    #[test]
+    fn paused_from_event() {
+        let event: EventPaused = serde_json::from_value(serde_json::json!({
+            "callFrames": [],
+            "reason": "other",
+            "hitBreakpoints": ["1:41:0:https://example.com/app.js"]
+        }))
+        .unwrap();
+        let paused = Paused::from_event(CdpEvent::DebuggerPaused(event)).unwrap();
+        assert_eq!(paused.reason, PausedReason::Other);
+        assert_eq!(
+            paused.hit_breakpoints,
+            vec![BreakpointId::from(
+                "1:41:0:https://example.com/app.js".to_string()
+            )]
+        );
+        assert!(paused.top_frame().is_none());
+        assert_eq!(paused.data, None);
+
+        let resumed = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert!(Paused::from_event(CdpEvent::DebuggerResumed(resumed)).is_none());
+    }
+}
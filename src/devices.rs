@@ -0,0 +1,119 @@
+//! Presets of popular devices to emulate via `Page::emulate`.
+//!
+//! Based on the device descriptors of [puppeteer](https://github.com/puppeteer/puppeteer/blob/v10.0.0/src/common/DeviceDescriptors.ts)
+
+use crate::handler::viewport::Viewport;
+
+/// A device, described by its user agent and screen
+#[derive(Debug, Clone, PartialEq)]
+pub struct Device {
+    /// The name of the device, like `"iPhone 13"`
+    pub name: &'static str,
+    /// The user agent of the device's default browser
+    pub user_agent: &'static str,
+    /// The viewport of the device in portrait orientation
+    pub viewport: Viewport,
+}
+
+impl Device {
+    /// The same device rotated into landscape orientation
+    pub fn landscape(&self) -> Self {
+        let mut device = self.clone();
+        device.viewport.width = self.viewport.height;
+        device.viewport.height = self.viewport.width;
+        device.viewport.is_landscape = true;
+        device
+    }
+}
+
+/// Returns the preset with the name, ignoring case
+pub fn find(name: &str) -> Option<&'static Device> {
+    ALL.iter()
+        .find(|device| device.name.eq_ignore_ascii_case(name))
+}
+
+pub const IPHONE_SE: Device = Device {
+    name: "iPhone SE",
+    user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 11_0 like Mac OS X) AppleWebKit/604.1.38 (KHTML, like Gecko) Version/11.0 Mobile/15A372 Safari/604.1",
+    viewport: mobile_viewport(320, 568, 2.),
+};
+
+pub const IPHONE_11: Device = Device {
+    name: "iPhone 11",
+    user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 13_7 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/12.0 Mobile/15A372 Safari/604.1",
+    viewport: mobile_viewport(414, 828, 2.),
+};
+
+pub const IPHONE_13: Device = Device {
+    name: "iPhone 13",
+    user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 15_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/15.0 Mobile/15E148 Safari/604.1",
+    viewport: mobile_viewport(390, 844, 3.),
+};
+
+pub const IPHONE_13_PRO_MAX: Device = Device {
+    name: "iPhone 13 Pro Max",
+    user_agent: "Mozilla/5.0 (iPhone; CPU iPhone OS 15_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/15.0 Mobile/15E148 Safari/604.1",
+    viewport: mobile_viewport(428, 926, 3.),
+};
+
+pub const PIXEL_4: Device = Device {
+    name: "Pixel 4",
+    user_agent: "Mozilla/5.0 (Linux; Android 10; Pixel 4) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/81.0.4044.138 Mobile Safari/537.36",
+    viewport: mobile_viewport(353, 745, 3.),
+};
+
+pub const PIXEL_5: Device = Device {
+    name: "Pixel 5",
+    user_agent: "Mozilla/5.0 (Linux; Android 11; Pixel 5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/90.0.4430.91 Mobile Safari/537.36",
+    viewport: mobile_viewport(393, 851, 2.75),
+};
+
+pub const GALAXY_S9_PLUS: Device = Device {
+    name: "Galaxy S9+",
+    user_agent: "Mozilla/5.0 (Linux; Android 8.0.0; SM-G965U Build/R16NW) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/63.0.3239.111 Mobile Safari/537.36",
+    viewport: mobile_viewport(320, 658, 4.5),
+};
+
+pub const IPAD: Device = Device {
+    name: "iPad",
+    user_agent: "Mozilla/5.0 (iPad; CPU OS 11_0 like Mac OS X) AppleWebKit/604.1.34 (KHTML, like Gecko) Version/11.0 Mobile/15A5341f Safari/604.1",
+    viewport: mobile_viewport(768, 1024, 2.),
+};
+
+pub const IPAD_MINI: Device = Device {
+    name: "iPad Mini",
+    user_agent: "Mozilla/5.0 (iPad; CPU OS 11_0 like Mac OS X) AppleWebKit/604.1.34 (KHTML, like Gecko) Version/11.0 Mobile/15A5341f Safari/604.1",
+    viewport: mobile_viewport(768, 1024, 2.),
+};
+
+pub const IPAD_PRO: Device = Device {
+    name: "iPad Pro",
+    user_agent: "Mozilla/5.0 (iPad; CPU OS 11_0 like Mac OS X) AppleWebKit/604.1.34 (KHTML, like Gecko) Version/11.0 Mobile/15A5341f Safari/604.1",
+    viewport: mobile_viewport(1024, 1366, 2.),
+};
+
+/// All presets of this module
+pub const ALL: [Device; 10] = [
+    IPHONE_SE,
+    IPHONE_11,
+    IPHONE_13,
+    IPHONE_13_PRO_MAX,
+    PIXEL_4,
+    PIXEL_5,
+    GALAXY_S9_PLUS,
+    IPAD,
+    IPAD_MINI,
+    IPAD_PRO,
+];
+
+/// A portrait viewport of a mobile device with touch support
+const fn mobile_viewport(width: u32, height: u32, device_scale_factor: f64) -> Viewport {
+    Viewport {
+        width,
+        height,
+        device_scale_factor: Some(device_scale_factor),
+        is_mobile: true,
+        is_landscape: false,
+        has_touch: true,
+    }
+}
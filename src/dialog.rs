@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    DialogType, EventJavascriptDialogOpening, HandleJavaScriptDialogParams,
+};
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+
+use crate::error::Result;
+use crate::handler::PageInner;
+
+/// Represents a javascript dialog (`alert`, `confirm`, `prompt` or
+/// `onbeforeunload`) that was opened by a page.
+///
+/// The page is blocked until the dialog is either accepted or dismissed.
+#[derive(Debug)]
+pub struct Dialog {
+    event: EventJavascriptDialogOpening,
+    tab: Arc<PageInner>,
+}
+
+impl Dialog {
+    pub(crate) fn from_event(tab: &Arc<PageInner>, event: CdpEvent) -> Option<Self> {
+        if let CdpEvent::PageJavascriptDialogOpening(ev) = event {
+            Some(Self {
+                event: ev,
+                tab: Arc::clone(tab),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The message displayed in the dialog
+    pub fn message(&self) -> &str {
+        &self.event.message
+    }
+
+    /// The type of the dialog
+    pub fn kind(&self) -> &DialogType {
+        &self.event.r#type
+    }
+
+    /// The default value of a `prompt` dialog, if any
+    pub fn default_prompt(&self) -> Option<&str> {
+        self.event.default_prompt.as_deref()
+    }
+
+    /// The url of the frame that opened the dialog
+    pub fn url(&self) -> &str {
+        &self.event.url
+    }
+
+    /// Accepts the dialog.
+    ///
+    /// The `prompt_text` is only used for `prompt` dialogs.
+    pub async fn accept(self, prompt_text: Option<String>) -> Result<()> {
+        let mut params = HandleJavaScriptDialogParams::new(true);
+        params.prompt_text = prompt_text;
+        self.tab.execute(params).await?;
+        Ok(())
+    }
+
+    /// Dismisses the dialog, this is equivalent to clicking `Cancel`
+    pub async fn dismiss(self) -> Result<()> {
+        self.tab
+            .execute(HandleJavaScriptDialogParams::new(false))
+            .await?;
+        Ok(())
+    }
+}
+
+/// How dialogs that are not handled via `Page::dialogs` are responded to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DialogPolicy {
+    /// Accept all dialogs, `prompt` dialogs are answered with their default
+    /// value.
+    Accept,
+    /// Dismiss all dialogs
+    Dismiss,
+}
+
+impl DialogPolicy {
+    /// The command that applies this policy to the dialog
+    pub(crate) fn handle_dialog(
+        &self,
+        event: &EventJavascriptDialogOpening,
+    ) -> HandleJavaScriptDialogParams {
+        match self {
+            DialogPolicy::Accept => {
+                let mut params = HandleJavaScriptDialogParams::new(true);
+                params.prompt_text = event.default_prompt.clone();
+                params
+            }
+            DialogPolicy::Dismiss => HandleJavaScriptDialogParams::new(false),
+        }
+    }
+}
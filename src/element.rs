@@ -1,19 +1,31 @@
+use std::path::Path;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use futures::{future, Future, FutureExt, Stream};
 
 use chromiumoxide_cdp::cdp::browser_protocol::dom::{
-    BackendNodeId, DescribeNodeParams, GetContentQuadsParams, Node, NodeId, ResolveNodeParams,
+    BackendNodeId, DescribeNodeParams, GetBoxModelParams, GetContentQuadsParams,
+    GetNodeForLocationParams, Node, NodeId, RequestNodeParams, ResolveNodeParams,
+    SetFileInputFilesParams,
 };
+use chromiumoxide_cdp::cdp::browser_protocol::input::MouseButton;
 use chromiumoxide_cdp::cdp::js_protocol::runtime::{
-    CallFunctionOnReturns, RemoteObjectId, RemoteObjectType,
+    CallArgument, CallFunctionOnParams, CallFunctionOnReturns, ReleaseObjectParams, RemoteObject,
+    RemoteObjectId, RemoteObjectType,
 };
 
+use crate::actionability::{self, ActionOptions, HIT_TARGET_CHECK, RETRY_BACKOFF_MS};
 use crate::error::{CdpError, Result};
+use crate::file_chooser;
 use crate::handler::PageInner;
-use crate::layout::{ElementQuad, Point};
+use crate::layout::{BoundingBox, BoxModel, ElementQuad, Point};
+use crate::mouse::ClickOptions;
+use crate::selector::Selector;
+use crate::touchscreen::Touchscreen;
+use crate::wait::{self, Polling, WaitOptions};
 
 /// Represents a [DOM Element](https://developer.mozilla.org/en-US/docs/Web/API/Element).
 #[derive(Debug)]
@@ -61,6 +73,36 @@ impl Element {
         })
     }
 
+    /// Create a new `Element` from the remote object of a DOM node.
+    ///
+    /// The document of the page must have been requested before.
+    pub(crate) async fn from_remote_object(
+        tab: Arc<PageInner>,
+        object_id: RemoteObjectId,
+    ) -> Result<Self> {
+        let node_id = tab
+            .execute(RequestNodeParams::new(object_id))
+            .await?
+            .node_id;
+        Element::new(tab, node_id).await
+    }
+
+    /// Converts the result of a wait operation into an `Element`, `None` if
+    /// the result does not represent a node.
+    pub(crate) async fn from_wait_result(
+        tab: &Arc<PageInner>,
+        obj: RemoteObject,
+    ) -> Result<Option<Self>> {
+        if let Some(object_id) = obj.object_id {
+            if obj.r#type == RemoteObjectType::Object {
+                return Ok(Some(
+                    Element::from_remote_object(Arc::clone(tab), object_id).await?,
+                ));
+            }
+        }
+        Ok(None)
+    }
+
     /// Convert a slice of `NodeId`s into a `Vec` of `Element`s
     pub(crate) async fn from_nodes(tab: &Arc<PageInner>, node_ids: &[NodeId]) -> Result<Vec<Self>> {
         Ok(future::join_all(
@@ -74,15 +116,16 @@ impl Element {
         .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Returns the first element in the document which matches the given CSS
-    /// selector.
-    pub async fn find_element(&self, selector: impl Into<String>) -> Result<Self> {
+    /// Returns the first element in the subtree of this element which matches
+    /// the given selector.
+    pub async fn find_element(&self, selector: impl Into<Selector>) -> Result<Self> {
         let node_id = self.tab.find_element(selector, self.node_id).await?;
         Ok(Element::new(Arc::clone(&self.tab), node_id).await?)
     }
 
-    /// Return all `Element`s in the document that match the given selector
-    pub async fn find_elements(&self, selector: impl Into<String>) -> Result<Vec<Element>> {
+    /// Return all `Element`s below this element that match the given
+    /// selector
+    pub async fn find_elements(&self, selector: impl Into<Selector>) -> Result<Vec<Element>> {
         Ok(Element::from_nodes(
             &self.tab,
             &self.tab.find_elements(selector, self.node_id).await?,
@@ -90,6 +133,75 @@ impl Element {
         .await?)
     }
 
+    /// Waits until an element matching the `selector` is present in the
+    /// subtree of this element and returns it.
+    ///
+    /// See `Page::wait_for_selector`
+    pub async fn wait_for_selector(
+        &self,
+        selector: impl Into<Selector>,
+        opts: WaitOptions,
+    ) -> Result<Option<Element>> {
+        let selector = selector.into();
+        let function_declaration = match wait::wait_for_selector_fn(&selector, &opts) {
+            Some(function_declaration) => function_declaration,
+            None => {
+                return Element::poll_for_selector(&self.tab, &selector, self.node_id, &opts).await
+            }
+        };
+        let obj = self
+            .tab
+            .wait_for(
+                function_declaration,
+                Some(self.remote_object_id.clone()),
+                || opts.describe_selector(&selector),
+                opts.timeout,
+            )
+            .await?;
+        Element::from_wait_result(&self.tab, obj).await
+    }
+
+    /// Waits for a selector that is not resolved inside the page, like an
+    /// ARIA role, by querying it repeatedly below the `node`.
+    pub(crate) async fn poll_for_selector(
+        tab: &Arc<PageInner>,
+        selector: &Selector,
+        node: NodeId,
+        opts: &WaitOptions,
+    ) -> Result<Option<Self>> {
+        let interval = match opts.polling {
+            Polling::Interval(interval) => interval,
+            Polling::Raf | Polling::Mutation => Duration::from_millis(100),
+        };
+        let poll = async {
+            loop {
+                let element = match tab.find_elements(selector.clone(), node).await?.first() {
+                    Some(node_id) => Some(Element::new(Arc::clone(tab), *node_id).await?),
+                    None => None,
+                };
+                let visible = match &element {
+                    Some(element) if opts.visible || opts.hidden => element.is_visible().await?,
+                    Some(_) => true,
+                    None => false,
+                };
+                match element {
+                    Some(element) if !opts.hidden && visible => return Ok(Some(element)),
+                    _ if opts.hidden && !visible => return Ok(None),
+                    _ => {}
+                }
+                async_std::task::sleep(interval).await;
+            }
+        };
+        async_std::future::timeout(opts.timeout, poll)
+            .await
+            .unwrap_or_else(|_| {
+                Err(CdpError::WaitTimeout {
+                    waiting_for: opts.describe_selector(selector),
+                    timeout: opts.timeout,
+                })
+            })
+    }
+
     /// Returns the best `Point` of this node to execute a click on.
     pub async fn clickable_point(&self) -> Result<Point> {
         let content_quads = self
@@ -111,6 +223,259 @@ impl Element {
             .ok_or_else(|| CdpError::msg("Node is either not visible or not an HTMLElement"))
     }
 
+    /// Returns the box model of this element, consisting of its content,
+    /// padding, border and margin quads.
+    ///
+    /// Fails if the element is not rendered, e.g. `display: none`.
+    pub async fn box_model(&self) -> Result<BoxModel> {
+        let model = self
+            .tab
+            .execute(
+                GetBoxModelParams::builder()
+                    .backend_node_id(self.backend_node_id)
+                    .build(),
+            )
+            .await?
+            .result
+            .model;
+        Ok(model.into())
+    }
+
+    /// Returns the bounding box of the element's border-box, relative to the
+    /// main frame's viewport.
+    pub async fn bounding_box(&self) -> Result<BoundingBox> {
+        Ok(self.box_model().await?.bounding_box())
+    }
+
+    /// Whether the element is attached to the document, has a non-empty
+    /// bounding box and no `visibility: hidden` style.
+    pub async fn is_visible(&self) -> Result<bool> {
+        self.bool_js_fn(
+            &"function() {
+                if (!this.isConnected) return false;
+                const element = this.nodeType === Node.TEXT_NODE ? this.parentElement : this;
+                return (IS_VISIBLE)(element);
+            }"
+            .replace("IS_VISIBLE", wait::IS_VISIBLE),
+            false,
+        )
+        .await
+    }
+
+    /// Negation of `Element::is_visible`
+    pub async fn is_hidden(&self) -> Result<bool> {
+        Ok(!self.is_visible().await?)
+    }
+
+    /// Whether the element is not disabled, neither natively nor via
+    /// `aria-disabled`.
+    pub async fn is_enabled(&self) -> Result<bool> {
+        self.bool_js_fn(
+            "function() {
+                if (this.nodeType !== Node.ELEMENT_NODE) return false;
+                return !this.matches(':disabled') && this.getAttribute('aria-disabled') !== 'true';
+            }",
+            false,
+        )
+        .await
+    }
+
+    /// Whether the checkbox or radio button is checked.
+    ///
+    /// Fails if the element is neither a checkbox nor a radio button,
+    /// natively or by its ARIA role.
+    pub async fn is_checked(&self) -> Result<bool> {
+        self.bool_js_fn(
+            "function() {
+                if (this.nodeType !== Node.ELEMENT_NODE) return 'Node is not of type HTMLElement';
+                if (this.nodeName === 'INPUT' && ['checkbox', 'radio'].includes(this.type))
+                    return this.checked;
+                if (['checkbox', 'radio', 'switch', 'menuitemcheckbox', 'menuitemradio']
+                    .includes(this.getAttribute('role')))
+                    return this.getAttribute('aria-checked') === 'true';
+                return 'Not a checkbox or radio button';
+            }",
+            false,
+        )
+        .await
+    }
+
+    /// Whether the element is enabled and accepts text input, meaning it is an
+    /// `input`, `textarea` or `select` that is not read-only, or is
+    /// `contenteditable`.
+    pub async fn is_editable(&self) -> Result<bool> {
+        if !self.is_enabled().await? {
+            return Ok(false);
+        }
+        self.bool_js_fn(
+            "function() {
+                if (['INPUT', 'TEXTAREA', 'SELECT'].includes(this.nodeName))
+                    return !this.readOnly;
+                return !!this.isContentEditable;
+            }",
+            false,
+        )
+        .await
+    }
+
+    /// Whether at least a part of the element intersects with the viewport.
+    pub async fn is_in_viewport(&self) -> Result<bool> {
+        self.bool_js_fn(
+            "async function() {
+                if (!this.isConnected || this.nodeType !== Node.ELEMENT_NODE) return false;
+                const visibleRatio = await new Promise(resolve => {
+                    const observer = new IntersectionObserver(entries => {
+                        resolve(entries[0].intersectionRatio);
+                        observer.disconnect();
+                    });
+                    observer.observe(this);
+                });
+                return visibleRatio > 0;
+            }",
+            true,
+        )
+        .await
+    }
+
+    /// Calls the function that either returns a boolean or an error message
+    async fn bool_js_fn(&self, function_declaration: &str, await_promise: bool) -> Result<bool> {
+        let resp = self.call_js_fn(function_declaration, await_promise).await?;
+        if let Some(details) = resp.exception_details {
+            return Err(CdpError::msg(details.text));
+        }
+        match resp.result.value {
+            Some(serde_json::Value::Bool(b)) => Ok(b),
+            Some(serde_json::Value::String(err)) => Err(CdpError::ChromeMessage(err)),
+            _ => Err(CdpError::NotFound),
+        }
+    }
+
+    /// Selects the options of this `<select>` element whose value or label
+    /// matches any of the `values`, deselecting all others, and dispatches the
+    /// `input` and `change` events.
+    ///
+    /// Returns the values of all options that are selected afterwards.
+    ///
+    /// # Example select an option by its value
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let select = page.find_element("select#color").await?;
+    ///     let selected = select.select_options(vec!["blue"]).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn select_options<I, S>(&self, values: I) -> Result<Vec<String>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let values: Vec<String> = values.into_iter().map(Into::into).collect();
+        self.wait_for_enabled(&ActionOptions::default()).await?;
+        let resp = self
+            .tab
+            .execute(
+                CallFunctionOnParams::builder()
+                    .object_id(self.remote_object_id.clone())
+                    .function_declaration(SELECT_OPTIONS)
+                    .argument(CallArgument::builder().value(values).build())
+                    .return_by_value(true)
+                    .build()
+                    .unwrap(),
+            )
+            .await?
+            .result;
+        if let Some(details) = resp.exception_details {
+            return Err(CdpError::msg(details.text));
+        }
+        match resp.result.value {
+            Some(serde_json::Value::String(err)) => Err(CdpError::ChromeMessage(err)),
+            Some(value) => Ok(serde_json::from_value(value)?),
+            None => Err(CdpError::NotFound),
+        }
+    }
+
+    /// Checks the checkbox or radio button by clicking it, unless it is
+    /// already checked.
+    ///
+    /// Fails if the element is not a checkbox or radio button, or if clicking
+    /// it did not check it.
+    pub async fn check(&self) -> Result<&Self> {
+        self.set_checked(true).await
+    }
+
+    /// Unchecks the checkbox by clicking it, unless it is already unchecked.
+    pub async fn uncheck(&self) -> Result<&Self> {
+        self.set_checked(false).await
+    }
+
+    async fn set_checked(&self, checked: bool) -> Result<&Self> {
+        if self.is_checked().await? == checked {
+            return Ok(self);
+        }
+        self.click().await?;
+        if self.is_checked().await? != checked {
+            return Err(CdpError::msg(
+                "Clicking the checkbox did not change its state",
+            ));
+        }
+        Ok(self)
+    }
+
+    /// Focuses the element, replaces its content with the `text` and
+    /// dispatches an `input` event.
+    ///
+    /// Works on `<input>`, `<textarea>` and `contenteditable` elements, after
+    /// waiting for them to be editable.
+    ///
+    /// # Example fill an input field
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.find_element("input#name").await?.fill("chromiumoxide").await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn fill(&self, text: impl AsRef<str>) -> Result<&Self> {
+        self.wait_for_editable(&ActionOptions::default()).await?;
+        let resp = self.call_js_fn(SELECT_CONTENT, false).await?;
+        if let Some(serde_json::Value::String(err)) = resp.result.value {
+            return Err(CdpError::ChromeMessage(err));
+        }
+        let text = text.as_ref();
+        if text.is_empty() {
+            self.tab.press_key("Delete").await?;
+        } else {
+            self.tab.insert_text(text).await?;
+        }
+        Ok(self)
+    }
+
+    /// Clears the content of the `<input>`, `<textarea>` or `contenteditable`
+    /// element.
+    pub async fn clear(&self) -> Result<&Self> {
+        self.fill("").await
+    }
+
+    /// Sets the files of this `<input type="file">` element.
+    ///
+    /// Relative paths are resolved against the current working directory, all
+    /// files must exist.
+    pub async fn set_input_files<I, P>(&self, paths: I) -> Result<&Self>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut params = SetFileInputFilesParams::new(file_chooser::absolute_paths(paths)?);
+        params.backend_node_id = Some(self.backend_node_id);
+        self.tab.execute(params).await?;
+        Ok(self)
+    }
+
     /// Submits a javascript function to the page and returns the evaluated
     /// result
     ///
@@ -196,13 +561,232 @@ impl Element {
     ///
     /// Bear in mind that if `click()` triggers a navigation this element may be
     /// not exist anymore.
+    ///
+    /// Waits until the element is actionable, see [`ActionOptions`].
     pub async fn click(&self) -> Result<&Self> {
-        let center = self.scroll_into_view().await?.clickable_point().await?;
-        self.tab.click(center).await?;
+        self.click_with(ActionOptions::default()).await
+    }
+
+    /// Clicks the center of the element, after waiting for it to be
+    /// actionable as configured by the options.
+    ///
+    /// # Example click an element without any actionability checks
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::element::Element;
+    /// # use chromiumoxide::actionability::ActionOptions;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(element: Element) -> Result<()> {
+    ///     element.click_with(ActionOptions::default().force(true)).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn click_with(&self, opts: impl Into<ActionOptions>) -> Result<&Self> {
+        let point = self.actionable_point(&opts.into()).await?;
+        self.tab.click(point).await?;
+        Ok(self)
+    }
+
+    /// Moves the mouse over the center of the element, after waiting for it
+    /// to be actionable.
+    pub async fn hover(&self) -> Result<&Self> {
+        let point = self.actionable_point(&ActionOptions::default()).await?;
+        self.tab.move_mouse_to_point(point).await?;
+        Ok(self)
+    }
+
+    /// Double clicks the center of the element, after waiting for it to be
+    /// actionable.
+    pub async fn double_click(&self) -> Result<&Self> {
+        self.click_button(ClickOptions::default().click_count(2))
+            .await
+    }
+
+    /// Clicks the center of the element with the right mouse button, after
+    /// waiting for it to be actionable.
+    pub async fn right_click(&self) -> Result<&Self> {
+        self.click_button(MouseButton::Right).await
+    }
+
+    /// Taps the center of the element, after waiting for it to be
+    /// actionable.
+    ///
+    /// The page must have touch emulation enabled to react to taps.
+    pub async fn tap(&self) -> Result<&Self> {
+        let point = self.actionable_point(&ActionOptions::default()).await?;
+        Touchscreen::new(Arc::clone(&self.tab)).tap(point).await?;
         Ok(self)
     }
 
-    /// Type the input
+    async fn click_button(&self, opts: impl Into<ClickOptions>) -> Result<&Self> {
+        let point = self.actionable_point(&ActionOptions::default()).await?;
+        self.tab.click_with(point, opts.into()).await?;
+        Ok(self)
+    }
+
+    /// Waits until the element is attached, visible, stable, enabled and
+    /// receives pointer events, and returns the point to click.
+    ///
+    /// With `force` set, the element is only scrolled into view.
+    pub(crate) async fn actionable_point(&self, opts: &ActionOptions) -> Result<Point> {
+        if opts.force {
+            return self.scroll_into_view().await?.clickable_point().await;
+        }
+        let deadline = Instant::now() + opts.timeout;
+        let mut attempt = 0;
+        loop {
+            let unmet = match self.unmet_state(true, false).await? {
+                Some(state) => format!("element to be {}", state),
+                None => {
+                    let point = self.scroll_into_view().await?.clickable_point().await?;
+                    if self.is_hit_target(point).await? {
+                        return Ok(point);
+                    }
+                    format!("element to receive pointer events at {:?}", point)
+                }
+            };
+            self.retry_delay(&mut attempt, deadline, unmet, opts.timeout)
+                .await?;
+        }
+    }
+
+    /// Waits until the element is attached, visible, enabled and editable.
+    pub(crate) async fn wait_for_editable(&self, opts: &ActionOptions) -> Result<&Self> {
+        self.wait_for_state(opts, true).await
+    }
+
+    /// Waits until the element is attached, visible and enabled.
+    pub(crate) async fn wait_for_enabled(&self, opts: &ActionOptions) -> Result<&Self> {
+        self.wait_for_state(opts, false).await
+    }
+
+    async fn wait_for_state(&self, opts: &ActionOptions, check_editable: bool) -> Result<&Self> {
+        if opts.force {
+            return Ok(self);
+        }
+        let deadline = Instant::now() + opts.timeout;
+        let mut attempt = 0;
+        while let Some(state) = self.unmet_state(false, check_editable).await? {
+            self.retry_delay(
+                &mut attempt,
+                deadline,
+                format!("element to be {}", state),
+                opts.timeout,
+            )
+            .await?;
+        }
+        Ok(self)
+    }
+
+    /// The first actionability state the element does not satisfy, if any
+    async fn unmet_state(
+        &self,
+        check_stable: bool,
+        check_editable: bool,
+    ) -> Result<Option<String>> {
+        let resp = self
+            .tab
+            .execute(
+                CallFunctionOnParams::builder()
+                    .object_id(self.remote_object_id.clone())
+                    .function_declaration(actionability::actionability_check())
+                    .argument(CallArgument::builder().value(check_stable).build())
+                    .argument(CallArgument::builder().value(check_editable).build())
+                    .await_promise(true)
+                    .return_by_value(true)
+                    .build()
+                    .unwrap(),
+            )
+            .await?
+            .result;
+        if let Some(details) = resp.exception_details {
+            return Err(CdpError::msg(details.text));
+        }
+        Ok(resp
+            .result
+            .value
+            .and_then(|v| v.as_str().map(str::to_string)))
+    }
+
+    /// Whether hit-testing the `point` yields this element or one of its
+    /// descendants, i.e. it is not covered by another element.
+    async fn is_hit_target(&self, point: Point) -> Result<bool> {
+        let hit = self
+            .tab
+            .execute(GetNodeForLocationParams::new(
+                point.x.round() as i64,
+                point.y.round() as i64,
+            ))
+            .await?
+            .result
+            .backend_node_id;
+        if hit == self.backend_node_id {
+            return Ok(true);
+        }
+        let hit_object = self
+            .tab
+            .execute(ResolveNodeParams::builder().backend_node_id(hit).build())
+            .await?
+            .result
+            .object
+            .object_id;
+        let hit_object = match hit_object {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+        // the hit node may live in a different execution context, in which
+        // case it is not part of this element
+        let is_target = self
+            .tab
+            .execute(
+                CallFunctionOnParams::builder()
+                    .object_id(self.remote_object_id.clone())
+                    .function_declaration(HIT_TARGET_CHECK)
+                    .argument(
+                        CallArgument::builder()
+                            .object_id(hit_object.clone())
+                            .build(),
+                    )
+                    .return_by_value(true)
+                    .build()
+                    .unwrap(),
+            )
+            .await
+            .ok()
+            .and_then(|resp| resp.result.result.value)
+            .and_then(|v| v.as_bool())
+            .unwrap_or_default();
+        self.tab
+            .execute(ReleaseObjectParams::new(hit_object))
+            .await?;
+        Ok(is_target)
+    }
+
+    /// Sleeps before the next actionability check, or fails with a
+    /// `CdpError::WaitTimeout` if the `deadline` would be exceeded.
+    async fn retry_delay(
+        &self,
+        attempt: &mut usize,
+        deadline: Instant,
+        waiting_for: String,
+        timeout: Duration,
+    ) -> Result<()> {
+        let delay =
+            Duration::from_millis(RETRY_BACKOFF_MS[(*attempt).min(RETRY_BACKOFF_MS.len() - 1)]);
+        *attempt += 1;
+        if Instant::now() + delay >= deadline {
+            return Err(CdpError::WaitTimeout {
+                waiting_for,
+                timeout,
+            });
+        }
+        async_std::task::sleep(delay).await;
+        Ok(())
+    }
+
+    /// Focuses the element and types the input
+    ///
+    /// Waits until the element is editable, see [`ActionOptions`].
     ///
     /// # Example type text into an input element
     ///
@@ -216,10 +800,38 @@ impl Element {
     /// # }
     /// ```
     pub async fn type_str(&self, input: impl AsRef<str>) -> Result<&Self> {
+        self.type_str_with(input, ActionOptions::default()).await
+    }
+
+    /// Focuses the element and types the input, after waiting for the
+    /// element to be attached, visible, enabled and editable.
+    ///
+    /// With `force` set the input is typed right away.
+    pub async fn type_str_with(
+        &self,
+        input: impl AsRef<str>,
+        opts: impl Into<ActionOptions>,
+    ) -> Result<&Self> {
+        self.wait_for_editable(&opts.into()).await?;
+        self.focus().await?;
         self.tab.type_str(input).await?;
         Ok(self)
     }
 
+    /// Focuses the element, unless it is already the active element.
+    pub async fn focus(&self) -> Result<&Self> {
+        self.call_js_fn(
+            "function() {
+                const root = this.getRootNode();
+                if (root.activeElement !== this && typeof this.focus === 'function')
+                    this.focus();
+            }",
+            false,
+        )
+        .await?;
+        Ok(self)
+    }
+
     /// Presses the key.
     ///
     /// # Example type text into an input element and hit enter
@@ -363,3 +975,37 @@ impl<'a> Stream for AttributeStream<'a> {
         Poll::Pending
     }
 }
+
+/// Selects all options of the `<select>` element whose value or label is in
+/// the `values` and returns the values of the selected options
+const SELECT_OPTIONS: &str = "function(values) {
+    if (this.nodeName !== 'SELECT') return 'Element is not a <select> element';
+    const options = Array.from(this.options);
+    this.value = undefined;
+    for (const option of options) {
+        option.selected = values.includes(option.value) || values.includes(option.label);
+        if (option.selected && !this.multiple) break;
+    }
+    this.dispatchEvent(new Event('input', { bubbles: true }));
+    this.dispatchEvent(new Event('change', { bubbles: true }));
+    return options.filter(option => option.selected).map(option => option.value);
+}";
+
+/// Focuses the element and selects all of its content, so that it is
+/// replaced by the next text input
+const SELECT_CONTENT: &str = "function() {
+    this.focus();
+    if (this.nodeName === 'INPUT' || this.nodeName === 'TEXTAREA') {
+        this.select();
+        return null;
+    }
+    if (this.isContentEditable) {
+        const range = this.ownerDocument.createRange();
+        range.selectNodeContents(this);
+        const selection = this.ownerDocument.defaultView.getSelection();
+        selection.removeAllRanges();
+        selection.addRange(range);
+        return null;
+    }
+    return 'Element is not an <input>, <textarea> or [contenteditable] element';
+}";
@@ -6,7 +6,7 @@ use base64::DecodeError;
 use chromiumoxide_cdp::cdp::browser_protocol::page::FrameId;
 use futures::channel::mpsc::SendError;
 use futures::channel::oneshot::Canceled;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 pub type Result<T, E = CdpError> = std::result::Result<T, E>;
@@ -39,6 +39,14 @@ pub enum CdpError {
     ScrollingFailed(String),
     #[error("Requested value not found.")]
     NotFound,
+    /// A wait operation like `Page::wait_for_selector` did not complete in
+    /// time
+    #[error("Timeout of {timeout:?} exceeded while waiting for {waiting_for}.")]
+    WaitTimeout {
+        /// Description of what was waited for
+        waiting_for: String,
+        timeout: Duration,
+    },
 }
 impl CdpError {
     pub fn msg(msg: impl Into<String>) -> Self {
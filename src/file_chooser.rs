@@ -0,0 +1,121 @@
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::channel::mpsc::UnboundedReceiver;
+use futures::{ready, Stream, StreamExt};
+
+use chromiumoxide_cdp::cdp::browser_protocol::dom::{BackendNodeId, SetFileInputFilesParams};
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    EventFileChooserOpened, FileChooserOpenedMode, FrameId,
+};
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+
+use crate::error::Result;
+use crate::handler::PageInner;
+
+/// Represents a file chooser dialog that was opened by a page, e.g. by
+/// clicking an `<input type="file">`.
+///
+/// While file choosers are intercepted, no native dialog is shown and the
+/// files have to be set via [`FileChooser::set_files`].
+#[derive(Debug)]
+pub struct FileChooser {
+    event: EventFileChooserOpened,
+    tab: Arc<PageInner>,
+}
+
+impl FileChooser {
+    pub(crate) fn from_event(tab: &Arc<PageInner>, event: CdpEvent) -> Option<Self> {
+        if let CdpEvent::PageFileChooserOpened(ev) = event {
+            Some(Self {
+                event: ev,
+                tab: Arc::clone(tab),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Whether multiple files can be selected
+    pub fn is_multiple(&self) -> bool {
+        self.event.mode == FileChooserOpenedMode::SelectMultiple
+    }
+
+    /// The id of the frame that contains the file input
+    pub fn frame_id(&self) -> &FrameId {
+        &self.event.frame_id
+    }
+
+    /// The backend node id of the file input that opened the chooser
+    pub fn backend_node_id(&self) -> BackendNodeId {
+        self.event.backend_node_id
+    }
+
+    /// Selects the files in the file input that opened the chooser.
+    ///
+    /// Relative paths are resolved against the current working directory.
+    pub async fn set_files<I, P>(self, paths: I) -> Result<()>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let mut params = SetFileInputFilesParams::new(absolute_paths(paths)?);
+        params.backend_node_id = Some(self.event.backend_node_id);
+        self.tab.execute(params).await?;
+        Ok(())
+    }
+}
+
+/// A stream of all file choosers opened by a page.
+///
+/// File choosers are intercepted as long as the stream exists, once the last
+/// stream of a page is dropped the native dialog is shown again.
+#[derive(Debug)]
+pub struct FileChoosers {
+    tab: Arc<PageInner>,
+    events: UnboundedReceiver<CdpEvent>,
+}
+
+impl FileChoosers {
+    pub(crate) async fn new(tab: Arc<PageInner>) -> Result<Self> {
+        let events = tab
+            .event_listener(vec![EventFileChooserOpened::IDENTIFIER])
+            .await?;
+        tab.intercept_file_choosers().await?;
+        Ok(Self { tab, events })
+    }
+}
+
+impl Stream for FileChoosers {
+    type Item = FileChooser;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while let Some(event) = ready!(this.events.poll_next_unpin(cx)) {
+            if let Some(chooser) = FileChooser::from_event(&this.tab, event) {
+                return Poll::Ready(Some(chooser));
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+impl Drop for FileChoosers {
+    fn drop(&mut self) {
+        self.tab.release_file_choosers();
+    }
+}
+
+/// Resolves all paths to absolute paths, failing if a file does not exist
+pub(crate) fn absolute_paths<I, P>(paths: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    paths
+        .into_iter()
+        .map(|path| Ok(path.as_ref().canonicalize()?.display().to_string()))
+        .collect()
+}
@@ -1,21 +1,121 @@
-use chromiumoxide_types::Method;
+use std::borrow::Cow;
+
+use chromiumoxide_types::{Command, Method};
 
 use crate::cmd::CommandChain;
 use crate::handler::viewport::Viewport;
 use chromiumoxide_cdp::cdp::browser_protocol::emulation::{
-    ScreenOrientation, ScreenOrientationType, SetDeviceMetricsOverrideParams,
+    MediaFeature, ScreenOrientation, ScreenOrientationType, SetCpuThrottlingRateParams,
+    SetDeviceMetricsOverrideParams, SetEmulatedMediaParams, SetEmulatedVisionDeficiencyParams,
+    SetGeolocationOverrideParams, SetLocaleOverrideParams, SetTimezoneOverrideParams,
     SetTouchEmulationEnabledParams,
 };
+use chromiumoxide_cdp::cdp::browser_protocol::network::SetUserAgentOverrideParams;
 
 #[derive(Debug, Default)]
 pub struct EmulationManager {
     pub emulating_mobile: bool,
     pub has_touch: bool,
     pub needs_reload: bool,
+    /// The emulated geolocation
+    geolocation: Option<SetGeolocationOverrideParams>,
+    /// The emulated timezone
+    timezone: Option<SetTimezoneOverrideParams>,
+    /// The emulated locale
+    locale: Option<SetLocaleOverrideParams>,
+    /// The emulated media type and media features
+    media: Option<SetEmulatedMediaParams>,
+    /// The emulated vision deficiency
+    vision_deficiency: Option<SetEmulatedVisionDeficiencyParams>,
+    /// The CPU throttling rate
+    cpu_throttling: Option<SetCpuThrottlingRateParams>,
+    /// The user agent, e.g. of an emulated device
+    user_agent: Option<SetUserAgentOverrideParams>,
 }
 
 impl EmulationManager {
-    pub fn init_commands(&mut self, viewport: &Viewport) -> CommandChain {
+    pub fn init_commands(&mut self, viewport: Option<&Viewport>) -> CommandChain {
+        let viewport = if let Some(viewport) = viewport {
+            viewport
+        } else {
+            return CommandChain::new(Vec::new());
+        };
+        let (set_device, set_touch) = Self::viewport_commands(viewport);
+
+        let chain = CommandChain::new(vec![
+            (
+                set_device.identifier(),
+                serde_json::to_value(set_device).unwrap(),
+            ),
+            (
+                set_touch.identifier(),
+                serde_json::to_value(set_touch).unwrap(),
+            ),
+        ]);
+
+        self.update_viewport(viewport);
+        chain
+    }
+
+    /// The command that applies the override, which for media overrides
+    /// includes the previously emulated media as well.
+    ///
+    /// The override is not tracked until it was applied, see
+    /// `track_override`.
+    pub(crate) fn resolve_override(&self, emulation: EmulationOverride) -> EmulationOverride {
+        match emulation {
+            EmulationOverride::MediaType(media) => {
+                let mut params = self.media.clone().unwrap_or_default();
+                params.media = media;
+                EmulationOverride::Media(params)
+            }
+            EmulationOverride::MediaFeatures(features) => {
+                let mut params = self.media.clone().unwrap_or_default();
+                params.features = Some(features);
+                EmulationOverride::Media(params)
+            }
+            emulation => emulation,
+        }
+    }
+
+    /// Tracks the resolved override after its command succeeded, so it is
+    /// re-applied after a crash
+    pub(crate) fn track_override(&mut self, emulation: EmulationOverride) {
+        match emulation {
+            EmulationOverride::Geolocation(params) => self.geolocation = Some(params),
+            EmulationOverride::Timezone(params) => self.timezone = Some(params),
+            EmulationOverride::Locale(params) => self.locale = Some(params),
+            EmulationOverride::Media(params) => self.media = Some(params),
+            EmulationOverride::VisionDeficiency(params) => self.vision_deficiency = Some(params),
+            EmulationOverride::CpuThrottling(params) => self.cpu_throttling = Some(params),
+            EmulationOverride::UserAgent(params) => self.user_agent = Some(params),
+            EmulationOverride::MediaType(_) | EmulationOverride::MediaFeatures(_) => {
+                unreachable!("merged into `EmulationOverride::Media` when resolved")
+            }
+        }
+    }
+
+    /// The commands that re-apply all tracked overrides, e.g. after the page
+    /// was reloaded following a crash
+    pub fn override_commands(&self) -> Vec<(Cow<'static, str>, serde_json::Value)> {
+        let mut cmds = Vec::new();
+        push_command(&mut cmds, self.geolocation.as_ref());
+        push_command(&mut cmds, self.timezone.as_ref());
+        push_command(&mut cmds, self.locale.as_ref());
+        push_command(&mut cmds, self.media.as_ref());
+        push_command(&mut cmds, self.vision_deficiency.as_ref());
+        push_command(&mut cmds, self.cpu_throttling.as_ref());
+        push_command(&mut cmds, self.user_agent.as_ref());
+        cmds
+    }
+
+    /// The commands that apply the viewport to a page
+    pub fn viewport_commands(
+        viewport: &Viewport,
+    ) -> (
+        SetDeviceMetricsOverrideParams,
+        SetTouchEmulationEnabledParams,
+    ) {
         let orientation = if viewport.is_landscape {
             ScreenOrientation::new(ScreenOrientationType::LandscapePrimary, 90)
         } else {
@@ -31,21 +131,100 @@ impl EmulationManager {
             .build()
             .unwrap();
 
-        let set_touch = SetTouchEmulationEnabledParams::new(true);
-
-        let chain = CommandChain::new(vec![
-            (
-                set_device.identifier(),
-                serde_json::to_value(set_device).unwrap(),
-            ),
-            (
-                set_touch.identifier(),
-                serde_json::to_value(set_touch).unwrap(),
-            ),
-        ]);
+        let mut set_touch = SetTouchEmulationEnabledParams::new(viewport.has_touch);
+        if viewport.has_touch {
+            set_touch.max_touch_points = Some(MAX_TOUCH_POINTS);
+        }
+        (set_device, set_touch)
+    }
 
+    /// Tracks the viewport that was applied and whether the page needs to be
+    /// reloaded for the change to take effect, which is the case if mobile or
+    /// touch emulation changed.
+    pub fn update_viewport(&mut self, viewport: &Viewport) -> bool {
         self.needs_reload =
             self.emulating_mobile != viewport.is_mobile || self.has_touch != viewport.has_touch;
-        chain
+        self.emulating_mobile = viewport.is_mobile;
+        self.has_touch = viewport.has_touch;
+        self.needs_reload
+    }
+}
+
+/// The number of simultaneous touch points supported when emulating touch
+const MAX_TOUCH_POINTS: i64 = 5;
+
+/// An emulation override requested by a page
+#[derive(Debug, Clone)]
+pub(crate) enum EmulationOverride {
+    Geolocation(SetGeolocationOverrideParams),
+    Timezone(SetTimezoneOverrideParams),
+    Locale(SetLocaleOverrideParams),
+    /// Only change the emulated media type
+    MediaType(Option<String>),
+    /// Only change the emulated media features
+    MediaFeatures(Vec<MediaFeature>),
+    Media(SetEmulatedMediaParams),
+    VisionDeficiency(SetEmulatedVisionDeficiencyParams),
+    CpuThrottling(SetCpuThrottlingRateParams),
+    UserAgent(SetUserAgentOverrideParams),
+}
+
+/// The media type a page is rendered for, see `Page::emulate_media_type`
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MediaType {
+    Screen,
+    Print,
+}
+
+impl AsRef<str> for MediaType {
+    fn as_ref(&self) -> &str {
+        match self {
+            MediaType::Screen => "screen",
+            MediaType::Print => "print",
+        }
+    }
+}
+
+fn push_command<C: Command>(
+    cmds: &mut Vec<(Cow<'static, str>, serde_json::Value)>,
+    cmd: Option<&C>,
+) {
+    if let Some(cmd) = cmd {
+        cmds.push((cmd.identifier(), serde_json::to_value(cmd).unwrap()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tracks_overrides_only_once_applied() {
+        let mut emulation = EmulationManager::default();
+        let resolved = emulation.resolve_override(EmulationOverride::Timezone(
+            SetTimezoneOverrideParams::new("Invalid/Zone"),
+        ));
+        assert!(emulation.override_commands().is_empty());
+
+        emulation.track_override(resolved);
+        let cmds = emulation.override_commands();
+        assert_eq!(cmds.len(), 1);
+        assert_eq!(cmds[0].0, "Emulation.setTimezoneOverride");
+    }
+
+    #[test]
+    fn merges_media_type_and_features() {
+        let mut emulation = EmulationManager::default();
+        let media =
+            emulation.resolve_override(EmulationOverride::MediaType(Some("print".to_string())));
+        emulation.track_override(media);
+        let feature = MediaFeature::new("prefers-color-scheme", "dark");
+        match emulation.resolve_override(EmulationOverride::MediaFeatures(vec![feature.clone()])) {
+            EmulationOverride::Media(params) => {
+                assert_eq!(params.media.as_deref(), Some("print"));
+                assert_eq!(params.features, Some(vec![feature]));
+            }
+            other => panic!("unexpected override {:?}", other),
+        }
     }
 }
@@ -90,6 +90,11 @@ impl Frame {
     pub fn is_loaded(&self) -> bool {
         self.lifecycle_events.contains("load")
     }
+
+    /// Whether the lifecycle event was fired for the current document
+    pub fn has_lifecycle_event(&self, event: &str) -> bool {
+        self.lifecycle_events.contains(event)
+    }
 }
 
 impl From<CdpFrame> for Frame {
@@ -346,6 +351,19 @@ impl FrameManager {
         }
     }
 
+    /// Forgets the lifecycle events of the main frame's current document.
+    ///
+    /// Commands like `Page.setDocumentContent` replace the document without
+    /// a new `init` lifecycle event, so the events of the previous document
+    /// would otherwise still be reported as fired.
+    pub fn clear_main_frame_lifecycle_events(&mut self) {
+        if let Some(id) = self.main_frame.as_ref() {
+            if let Some(frame) = self.frames.get_mut(id) {
+                frame.lifecycle_events.clear();
+            }
+        }
+    }
+
     /// Detach all child frames
     fn remove_frames_recursively(&mut self, id: &FrameId) -> Option<Frame> {
         if let Some(mut frame) = self.frames.remove(id) {
@@ -422,7 +440,8 @@ impl NavigationOk {
     }
 }
 
-/// Tracks the progress of an issued `Page.navigate` request until completion.
+/// Tracks the progress of an issued navigation request like `Page.navigate`
+/// or `Page.reload` until completion.
 #[derive(Debug)]
 pub struct NavigationWatcher {
     id: NavigationId,
@@ -478,7 +497,12 @@ impl FrameNavigationRequest {
         }
     }
 
+    /// Inserts the frame to navigate in a `Page.navigate` request, other
+    /// navigations always target the main frame
     pub fn set_frame_id(&mut self, frame_id: FrameId) {
+        if self.req.method.as_ref() != page::NavigateParams::IDENTIFIER {
+            return;
+        }
         if let Some(params) = self.req.params.as_object_mut() {
             if let Entry::Vacant(entry) = params.entry("frameId") {
                 entry.insert(serde_json::Value::String(frame_id.into()));
@@ -486,3 +510,56 @@ impl FrameNavigationRequest {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lifecycle_event(name: &str) -> EventLifecycleEvent {
+        serde_json::from_value(serde_json::json!({
+            "frameId": "main",
+            "loaderId": "loader",
+            "name": name,
+            "timestamp": 1.0
+        }))
+        .unwrap()
+    }
+
+    fn loaded_frame_manager() -> FrameManager {
+        let mut manager = FrameManager::default();
+        manager.on_frame_navigated(
+            serde_json::from_value(serde_json::json!({
+                "id": "main",
+                "loaderId": "loader",
+                "url": "https://example.com/",
+                "domainAndRegistry": "example.com",
+                "securityOrigin": "https://example.com",
+                "mimeType": "text/html",
+                "secureContextType": "Secure",
+                "crossOriginIsolatedContextType": "NotIsolated"
+            }))
+            .unwrap(),
+        );
+        for name in &["init", "DOMContentLoaded", "load"] {
+            manager.on_page_lifecycle_event(&lifecycle_event(name));
+        }
+        manager
+    }
+
+    #[test]
+    fn cleared_lifecycle_events_wait_for_the_next_document() {
+        let mut manager = loaded_frame_manager();
+        assert!(manager.main_frame().unwrap().has_lifecycle_event("load"));
+
+        manager.clear_main_frame_lifecycle_events();
+        let main = manager.main_frame().unwrap();
+        assert!(!main.has_lifecycle_event("load"));
+        assert!(!main.has_lifecycle_event("DOMContentLoaded"));
+
+        // the replaced document fires its events without a new `init`
+        manager.on_page_lifecycle_event(&lifecycle_event("DOMContentLoaded"));
+        let main = manager.main_frame().unwrap();
+        assert!(main.has_lifecycle_event("DOMContentLoaded"));
+        assert!(!main.is_loaded());
+    }
+}
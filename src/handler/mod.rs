@@ -19,6 +19,7 @@ pub(crate) use page::PageInner;
 
 use crate::cmd::{to_command_response, CommandMessage};
 use crate::conn::Connection;
+use crate::dialog::DialogPolicy;
 use crate::error::Result;
 use crate::handler::browser::BrowserContext;
 use crate::handler::frame::FrameNavigationRequest;
@@ -27,6 +28,7 @@ use crate::handler::job::PeriodicJob;
 use crate::handler::session::Session;
 use crate::handler::target::Target;
 use crate::handler::target::TargetEvent;
+use crate::handler::viewport::Viewport;
 use crate::page::Page;
 
 /// Standard timeout in MS
@@ -40,7 +42,7 @@ pub mod network;
 mod page;
 mod session;
 pub mod target;
-mod viewport;
+pub mod viewport;
 
 /// The handler that monitors the state of the chromium browser and drives all
 /// the requests and events.
@@ -70,12 +72,18 @@ pub struct Handler {
     evict_command_timeout: PeriodicJob,
     /// The internal identifier for a specific navigation
     next_navigation_id: usize,
+    /// How the handler and its targets are configured
+    config: HandlerConfig,
 }
 
 impl Handler {
     /// Create a new `Handler` that drives the connection and listens for
     /// messages on the receiver `rx`.
-    pub(crate) fn new(mut conn: Connection<CdpEventMessage>, rx: Receiver<HandlerMessage>) -> Self {
+    pub(crate) fn new(
+        mut conn: Connection<CdpEventMessage>,
+        rx: Receiver<HandlerMessage>,
+        config: HandlerConfig,
+    ) -> Self {
         let discover = SetDiscoverTargetsParams::new(true);
         let _ = conn.submit_command(
             discover.identifier(),
@@ -94,6 +102,7 @@ impl Handler {
             conn,
             evict_command_timeout: Default::default(),
             next_navigation_id: 0,
+            config,
         }
     }
 
@@ -316,7 +325,7 @@ impl Handler {
     ///
     /// Creates a new `Target` instance and keeps track of it
     fn on_target_created(&mut self, event: EventTargetCreated) {
-        let target = Target::new(event.target_info);
+        let target = Target::new(event.target_info, &self.config);
         self.target_ids.push(target.target_id().clone());
         self.targets.insert(target.target_id().clone(), target);
     }
@@ -448,6 +457,29 @@ impl Stream for Handler {
     }
 }
 
+/// How the `Handler` and the `Target`s it creates are configured
+#[derive(Debug, Clone)]
+pub struct HandlerConfig {
+    /// How javascript dialogs are responded to that are not handled via
+    /// `Page::dialogs`.
+    ///
+    /// If `None`, those dialogs remain open and block their page.
+    pub dialog_policy: Option<DialogPolicy>,
+    /// The viewport new pages are emulated with.
+    ///
+    /// If `None`, pages use the size of the browser window.
+    pub viewport: Option<Viewport>,
+}
+
+impl Default for HandlerConfig {
+    fn default() -> Self {
+        Self {
+            dialog_policy: None,
+            viewport: Some(Default::default()),
+        }
+    }
+}
+
 /// Wraps the sender half of the channel who requested a navigation
 #[derive(Debug)]
 pub struct NavigationInProgress<T> {
@@ -482,9 +514,9 @@ impl<T> NavigationInProgress<T> {
 /// Request type for navigation
 #[derive(Debug)]
 enum NavigationRequest {
-    /// Represents a simple `NavigateParams` ("Page.navigate")
+    /// Represents a request that navigates the main frame: `Page.navigate`,
+    /// `Page.reload` or `Page.navigateToHistoryEntry`
     Navigate(NavigationInProgress<Result<Response>>),
-    // TODO are there more?
 }
 
 /// Different kind of submitted request submitted from the  `Handler` to the
@@ -1,11 +1,17 @@
-use chromiumoxide_types::Method;
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use chromiumoxide_types::{Command, Method};
 
 use crate::cmd::CommandChain;
 use chromiumoxide_cdp::cdp::browser_protocol::fetch::{EventAuthRequired, EventRequestPaused};
 use chromiumoxide_cdp::cdp::browser_protocol::network::{
-    EventLoadingFailed, EventLoadingFinished, EventRequestServedFromCache, EventRequestWillBeSent,
-    EventResponseReceived,
+    EmulateNetworkConditionsParams, EventLoadingFailed, EventLoadingFinished,
+    EventRequestServedFromCache, EventRequestWillBeSent, EventResponseReceived, Headers, LoaderId,
+    RequestId, ResourceType, Response, SecurityDetails, SetCacheDisabledParams,
+    SetExtraHttpHeadersParams,
 };
+use chromiumoxide_cdp::cdp::browser_protocol::page::{FrameId, SetBypassCspParams};
 use chromiumoxide_cdp::cdp::browser_protocol::{
     network::EnableParams, security::SetIgnoreCertificateErrorsParams,
 };
@@ -13,11 +19,93 @@ use chromiumoxide_cdp::cdp::browser_protocol::{
 #[derive(Debug)]
 pub struct NetworkManager {
     ignore_httpserrors: bool,
+    /// Whether the network is emulated to be offline
+    offline: bool,
+    /// The emulated network conditions, if any
+    conditions: Option<NetworkConditions>,
+    /// Whether the browser cache is used
+    cache_enabled: bool,
+    /// Headers sent with every request of the page
+    extra_headers: Option<SetExtraHttpHeadersParams>,
+    /// Whether the page's content security policy is bypassed
+    bypass_csp: bool,
+    /// The response of the current document of each frame
+    document_responses: HashMap<FrameId, HttpResponse>,
 }
 
 impl NetworkManager {
     pub fn new(ignore_httpserrors: bool) -> Self {
-        Self { ignore_httpserrors }
+        Self {
+            ignore_httpserrors,
+            offline: false,
+            conditions: None,
+            cache_enabled: true,
+            extra_headers: None,
+            bypass_csp: false,
+            document_responses: Default::default(),
+        }
+    }
+
+    /// The command that applies the override, which for offline mode and
+    /// network conditions includes the other as well.
+    ///
+    /// The override is not tracked until it was applied, see
+    /// `track_override`.
+    pub(crate) fn resolve_override(&self, network: NetworkOverride) -> NetworkOverride {
+        match network {
+            NetworkOverride::Offline(offline) => {
+                NetworkOverride::Conditions(network_conditions_params(offline, self.conditions))
+            }
+            NetworkOverride::NetworkConditions(conditions) => {
+                NetworkOverride::Conditions(network_conditions_params(self.offline, conditions))
+            }
+            network => network,
+        }
+    }
+
+    /// Tracks the resolved override after its command succeeded, so it is
+    /// applied to targets that are attached later on
+    pub(crate) fn track_override(&mut self, network: NetworkOverride) {
+        match network {
+            NetworkOverride::Conditions(params) => {
+                self.offline = params.offline;
+                let conditions = NetworkConditions {
+                    latency: params.latency,
+                    download_throughput: params.download_throughput,
+                    upload_throughput: params.upload_throughput,
+                };
+                self.conditions =
+                    Some(conditions).filter(|c| *c != NetworkConditions::NO_THROTTLING);
+            }
+            NetworkOverride::CacheDisabled(params) => self.cache_enabled = !params.cache_disabled,
+            NetworkOverride::ExtraHeaders(params) => self.extra_headers = Some(params),
+            NetworkOverride::BypassCsp(params) => self.bypass_csp = params.enabled,
+            NetworkOverride::Offline(_) | NetworkOverride::NetworkConditions(_) => {
+                unreachable!("merged into `NetworkOverride::Conditions` when resolved")
+            }
+        }
+    }
+
+    /// The commands that re-apply all tracked overrides, e.g. after the page
+    /// was reloaded following a crash
+    pub fn override_commands(&self) -> Vec<(Cow<'static, str>, serde_json::Value)> {
+        let mut cmds = Vec::new();
+        if self.offline || self.conditions.is_some() {
+            push_command(
+                &mut cmds,
+                &network_conditions_params(self.offline, self.conditions),
+            );
+        }
+        if !self.cache_enabled {
+            push_command(&mut cmds, &SetCacheDisabledParams::new(true));
+        }
+        if let Some(headers) = self.extra_headers.as_ref() {
+            push_command(&mut cmds, headers);
+        }
+        if self.bypass_csp {
+            push_command(&mut cmds, &SetBypassCspParams::new(true));
+        }
+        cmds
     }
 
     pub fn init_commands(&self) -> CommandChain {
@@ -44,15 +132,293 @@ impl NetworkManager {
 
     pub fn on_request_served_from_cache(&mut self, _event: &EventRequestServedFromCache) {}
 
-    pub fn on_response_received(&mut self, _event: &EventResponseReceived) {}
+    pub fn on_response_received(&mut self, event: &EventResponseReceived) {
+        if event.r#type != ResourceType::Document {
+            return;
+        }
+        if let Some(frame_id) = event.frame_id.clone() {
+            self.document_responses.insert(
+                frame_id,
+                HttpResponse {
+                    request_id: event.request_id.clone(),
+                    loader_id: event.loader_id.clone(),
+                    response: event.response.clone(),
+                },
+            );
+        }
+    }
+
+    /// The response of the document the frame loaded with the loader
+    pub fn document_response(
+        &self,
+        frame_id: &FrameId,
+        loader_id: &LoaderId,
+    ) -> Option<&HttpResponse> {
+        self.document_responses
+            .get(frame_id)
+            .filter(|resp| &resp.loader_id == loader_id)
+    }
+
+    /// Forgets the response of a frame that was detached
+    pub fn on_frame_detached(&mut self, frame_id: &FrameId) {
+        self.document_responses.remove(frame_id);
+    }
 
     pub fn on_network_loading_finished(&mut self, _event: &EventLoadingFinished) {}
 
     pub fn on_network_loading_failed(&mut self, _event: &EventLoadingFailed) {}
 }
 
+/// The command that emulates the offline mode and network conditions
+fn network_conditions_params(
+    offline: bool,
+    conditions: Option<NetworkConditions>,
+) -> EmulateNetworkConditionsParams {
+    let conditions = conditions.unwrap_or(NetworkConditions::NO_THROTTLING);
+    EmulateNetworkConditionsParams::new(
+        offline,
+        conditions.latency,
+        conditions.download_throughput,
+        conditions.upload_throughput,
+    )
+}
+
 impl Default for NetworkManager {
     fn default() -> Self {
         NetworkManager::new(true)
     }
 }
+
+/// The response to the request of a document, like the main resource of a
+/// navigation
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpResponse {
+    request_id: RequestId,
+    loader_id: LoaderId,
+    response: Response,
+}
+
+impl HttpResponse {
+    /// The url of the response, after all redirects
+    pub fn url(&self) -> &str {
+        &self.response.url
+    }
+
+    /// The HTTP status code
+    pub fn status(&self) -> i64 {
+        self.response.status
+    }
+
+    pub fn status_text(&self) -> &str {
+        &self.response.status_text
+    }
+
+    /// Whether the status is in the range 200-299
+    pub fn ok(&self) -> bool {
+        (200..300).contains(&self.response.status)
+    }
+
+    pub fn headers(&self) -> &Headers {
+        &self.response.headers
+    }
+
+    /// The value of the header, the name is matched case-insensitively
+    pub fn header(&self, name: impl AsRef<str>) -> Option<&str> {
+        let name = name.as_ref();
+        self.response
+            .headers
+            .inner()
+            .as_object()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .and_then(|(_, value)| value.as_str())
+    }
+
+    pub fn mime_type(&self) -> &str {
+        &self.response.mime_type
+    }
+
+    /// The address of the server as `ip:port`, if known
+    pub fn remote_address(&self) -> Option<String> {
+        let ip = self.response.remote_ip_address.as_ref()?;
+        Some(match self.response.remote_port {
+            Some(port) => format!("{}:{}", ip, port),
+            None => ip.clone(),
+        })
+    }
+
+    /// The TLS details of the connection, `None` for plain HTTP
+    pub fn security_details(&self) -> Option<&SecurityDetails> {
+        self.response.security_details.as_ref()
+    }
+
+    /// Whether the response was served from the disk cache
+    pub fn from_cache(&self) -> bool {
+        self.response.from_disk_cache.unwrap_or_default()
+    }
+
+    pub fn request_id(&self) -> &RequestId {
+        &self.request_id
+    }
+
+    /// The loader that loaded the document, as returned by `Page.navigate`
+    pub fn loader_id(&self) -> &LoaderId {
+        &self.loader_id
+    }
+
+    /// The response as reported by chromium
+    pub fn response(&self) -> &Response {
+        &self.response
+    }
+}
+
+/// A network override requested by a page
+#[derive(Debug, Clone)]
+pub(crate) enum NetworkOverride {
+    /// Only change the offline mode
+    Offline(bool),
+    /// Only change the network conditions
+    NetworkConditions(Option<NetworkConditions>),
+    Conditions(EmulateNetworkConditionsParams),
+    CacheDisabled(SetCacheDisabledParams),
+    ExtraHeaders(SetExtraHttpHeadersParams),
+    BypassCsp(SetBypassCspParams),
+}
+
+/// Throughput and latency of an emulated network connection
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NetworkConditions {
+    /// Minimum latency from request sent to response headers received in ms
+    pub latency: f64,
+    /// Maximal aggregated download throughput in bytes/sec, -1 disables
+    /// download throttling.
+    pub download_throughput: f64,
+    /// Maximal aggregated upload throughput in bytes/sec, -1 disables upload
+    /// throttling.
+    pub upload_throughput: f64,
+}
+
+impl NetworkConditions {
+    /// No latency and unlimited throughput
+    pub const NO_THROTTLING: NetworkConditions = NetworkConditions {
+        latency: 0.,
+        download_throughput: -1.,
+        upload_throughput: -1.,
+    };
+
+    /// The "Slow 3G" preset of the chromium devtools
+    pub const SLOW_3G: NetworkConditions = NetworkConditions {
+        latency: 400. * 5.,
+        download_throughput: 500. * 1000. / 8. * 0.8,
+        upload_throughput: 500. * 1000. / 8. * 0.8,
+    };
+
+    /// The "Fast 3G" preset of the chromium devtools
+    pub const FAST_3G: NetworkConditions = NetworkConditions {
+        latency: 150. * 3.75,
+        download_throughput: 1.6 * 1000. * 1000. / 8. * 0.9,
+        upload_throughput: 750. * 1000. / 8. * 0.9,
+    };
+}
+
+fn push_command<C: Command>(cmds: &mut Vec<(Cow<'static, str>, serde_json::Value)>, cmd: &C) {
+    cmds.push((cmd.identifier(), serde_json::to_value(cmd).unwrap()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conditions(network: NetworkOverride) -> EmulateNetworkConditionsParams {
+        match network {
+            NetworkOverride::Conditions(params) => params,
+            other => panic!("unexpected override {:?}", other),
+        }
+    }
+
+    fn command_names(network: &NetworkManager) -> Vec<Cow<'static, str>> {
+        network
+            .override_commands()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn tracks_overrides_only_once_applied() {
+        let mut network = NetworkManager::default();
+        let resolved = network.resolve_override(NetworkOverride::Offline(true));
+        assert!(network.override_commands().is_empty());
+
+        network.track_override(resolved);
+        assert_eq!(
+            command_names(&network),
+            vec!["Network.emulateNetworkConditions"]
+        );
+    }
+
+    #[test]
+    fn merges_offline_mode_and_network_conditions() {
+        let mut network = NetworkManager::default();
+        let resolved = network.resolve_override(NetworkOverride::NetworkConditions(Some(
+            NetworkConditions::SLOW_3G,
+        )));
+        network.track_override(resolved);
+
+        let params = conditions(network.resolve_override(NetworkOverride::Offline(true)));
+        assert!(params.offline);
+        assert_eq!(params.latency, NetworkConditions::SLOW_3G.latency);
+        assert_eq!(
+            params.download_throughput,
+            NetworkConditions::SLOW_3G.download_throughput
+        );
+        network.track_override(NetworkOverride::Conditions(params));
+
+        let params = conditions(network.resolve_override(NetworkOverride::NetworkConditions(None)));
+        assert!(params.offline);
+        assert_eq!(params.latency, NetworkConditions::NO_THROTTLING.latency);
+        assert_eq!(
+            params.upload_throughput,
+            NetworkConditions::NO_THROTTLING.upload_throughput
+        );
+    }
+
+    #[test]
+    fn untracks_conditions_without_emulation() {
+        let mut network = NetworkManager::default();
+        let offline = network.resolve_override(NetworkOverride::Offline(true));
+        network.track_override(offline);
+        let online = network.resolve_override(NetworkOverride::Offline(false));
+        network.track_override(online);
+        assert!(network.override_commands().is_empty());
+    }
+
+    #[test]
+    fn tracks_cache_headers_and_csp() {
+        let mut network = NetworkManager::default();
+        for override_ in [
+            NetworkOverride::CacheDisabled(SetCacheDisabledParams::new(true)),
+            NetworkOverride::ExtraHeaders(SetExtraHttpHeadersParams::new(
+                serde_json::from_value::<Headers>(serde_json::json!({ "x-test": "1" })).unwrap(),
+            )),
+            NetworkOverride::BypassCsp(SetBypassCspParams::new(true)),
+        ] {
+            let resolved = network.resolve_override(override_);
+            network.track_override(resolved);
+        }
+        assert_eq!(
+            command_names(&network),
+            vec![
+                "Network.setCacheDisabled",
+                "Network.setExtraHTTPHeaders",
+                "Page.setBypassCSP"
+            ]
+        );
+
+        let enabled = network.resolve_override(NetworkOverride::CacheDisabled(
+            SetCacheDisabledParams::new(false),
+        ));
+        network.track_override(enabled);
+        assert_eq!(command_names(&network).len(), 2);
+    }
+}
@@ -1,26 +1,49 @@
+use crate::handler::emulation::{EmulationManager, EmulationOverride};
+use crate::handler::network::NetworkOverride;
 use crate::handler::target::TargetMessage;
+use crate::handler::viewport::Viewport;
 use chromiumoxide_cdp::cdp::browser_protocol::target::{SessionId, TargetId};
 use chromiumoxide_types::{Command, CommandResponse};
-use futures::channel::mpsc::{channel, Receiver, Sender};
+use futures::channel::mpsc::{channel, unbounded, Receiver, Sender, UnboundedReceiver};
 use futures::channel::oneshot::channel as oneshot_channel;
+use futures::future::BoxFuture;
 use futures::stream::Fuse;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use crate::cmd::{to_command_response, CommandMessage};
+use crate::coverage::CoverageState;
+use crate::debugger::DebuggerState;
 use crate::error::{CdpError, Result};
-use crate::keys;
+use crate::io::IoExecutor;
+use crate::keyboard::{split_combo, KeyboardState};
+use crate::keys::{KeyDefinition, KeyboardLayout};
 use crate::layout::Point;
+use crate::listeners::EventListenerRequest;
+use crate::mouse::{button_mask, ClickOptions, MouseState};
+use crate::selector::Selector;
+use chromiumoxide_cdp::cdp::browser_protocol::accessibility::QueryAxTreeParams;
 use chromiumoxide_cdp::cdp::browser_protocol::dom::{
-    NodeId, QuerySelectorAllParams, QuerySelectorParams,
+    NodeId, PushNodesByBackendIdsToFrontendParams, QuerySelectorAllParams, QuerySelectorParams,
+    RequestNodeParams, ResolveNodeParams,
 };
 use chromiumoxide_cdp::cdp::browser_protocol::input::{
-    DispatchKeyEventParams, DispatchKeyEventType, DispatchMouseEventParams, DispatchMouseEventType,
-    MouseButton,
+    DispatchKeyEventParams, DispatchKeyEventParamsBuilder, DispatchKeyEventType,
+    DispatchMouseEventParams, DispatchMouseEventType, DispatchTouchEventParams,
+    DispatchTouchEventType, InsertTextParams, MouseButton, TouchPoint,
 };
+use chromiumoxide_cdp::cdp::browser_protocol::io::{CloseParams, ReadParams, ReadReturns};
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    ReloadParams, SetInterceptFileChooserDialogParams,
+};
+use chromiumoxide_cdp::cdp::events::CdpEvent;
 use chromiumoxide_cdp::cdp::js_protocol::runtime::{
-    CallFunctionOnParams, CallFunctionOnReturns, RemoteObjectId,
+    CallFunctionOnParams, CallFunctionOnReturns, EvaluateParams, GetPropertiesParams,
+    ReleaseObjectParams, RemoteObject, RemoteObjectId, RemoteObjectType,
 };
 use futures::{SinkExt, StreamExt};
+use std::borrow::Cow;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub struct PageHandle {
@@ -35,6 +58,11 @@ impl PageHandle {
             target_id,
             session_id,
             sender: commands,
+            mouse: Default::default(),
+            keyboard: Default::default(),
+            file_chooser_streams: Default::default(),
+            coverage: Default::default(),
+            debugger: Default::default(),
         };
         Self {
             rx: rx.fuse(),
@@ -52,6 +80,16 @@ pub(crate) struct PageInner {
     target_id: TargetId,
     session_id: SessionId,
     sender: Sender<TargetMessage>,
+    /// Position and pressed buttons of the page's mouse
+    mouse: Mutex<MouseState>,
+    /// Held modifiers and keys and the layout of the page's keyboard
+    keyboard: Mutex<KeyboardState>,
+    /// Number of `FileChoosers` streams that keep file choosers intercepted
+    file_chooser_streams: AtomicUsize,
+    /// The coverage that is collected, shared by all `Coverage` handles
+    coverage: CoverageState,
+    /// The scripts tracked by the debugger, shared by all `Debugger` handles
+    debugger: DebuggerState,
 }
 
 impl PageInner {
@@ -71,6 +109,100 @@ impl PageInner {
         Ok(rx.await??)
     }
 
+    /// Applies the viewport and reloads the page if mobile or touch emulation
+    /// changed
+    pub(crate) async fn set_viewport(&self, viewport: Viewport) -> Result<&Self> {
+        let (set_device, set_touch) = EmulationManager::viewport_commands(&viewport);
+        self.execute(set_device).await?;
+        self.execute(set_touch).await?;
+
+        let (tx, rx) = oneshot_channel();
+        self.sender
+            .clone()
+            .send(TargetMessage::ViewportChanged(viewport, tx))
+            .await?;
+        if rx.await? {
+            // reloading is a navigation and resolves once the page is loaded
+            self.execute(ReloadParams::default()).await?;
+        }
+        Ok(self)
+    }
+
+    /// Applies the emulation override, which is tracked by the target once
+    /// it succeeded so it can be re-applied
+    pub(crate) async fn emulate(&self, emulation: EmulationOverride) -> Result<&Self> {
+        let (tx, rx) = oneshot_channel();
+        self.sender
+            .clone()
+            .send(TargetMessage::Emulate(emulation, tx))
+            .await?;
+        let resolved = rx.await?;
+        match resolved.clone() {
+            EmulationOverride::Geolocation(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::Timezone(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::Locale(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::Media(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::VisionDeficiency(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::CpuThrottling(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::UserAgent(params) => {
+                self.execute(params).await?;
+            }
+            EmulationOverride::MediaType(_) | EmulationOverride::MediaFeatures(_) => {
+                unreachable!("merged into `EmulationOverride::Media` by the target")
+            }
+        }
+        self.sender
+            .clone()
+            .send(TargetMessage::EmulationApplied(resolved))
+            .await?;
+        Ok(self)
+    }
+
+    /// Applies the network override, which is tracked by the target once it
+    /// succeeded so it can be re-applied
+    pub(crate) async fn override_network(&self, network: NetworkOverride) -> Result<&Self> {
+        let (tx, rx) = oneshot_channel();
+        self.sender
+            .clone()
+            .send(TargetMessage::Network(network, tx))
+            .await?;
+        let resolved = rx.await?;
+        match resolved.clone() {
+            NetworkOverride::Conditions(params) => {
+                self.execute(params).await?;
+            }
+            NetworkOverride::CacheDisabled(params) => {
+                self.execute(params).await?;
+            }
+            NetworkOverride::ExtraHeaders(params) => {
+                self.execute(params).await?;
+            }
+            NetworkOverride::BypassCsp(params) => {
+                self.execute(params).await?;
+            }
+            NetworkOverride::Offline(_) | NetworkOverride::NetworkConditions(_) => {
+                unreachable!("merged into `NetworkOverride::Conditions` by the target")
+            }
+        }
+        self.sender
+            .clone()
+            .send(TargetMessage::NetworkApplied(resolved))
+            .await?;
+        Ok(self)
+    }
+
     /// The identifier of this page's target
     pub fn target_id(&self) -> &TargetId {
         &self.target_id
@@ -81,66 +213,326 @@ impl PageInner {
         &self.session_id
     }
 
+    /// Sends the command without waiting for the response
+    pub(crate) fn send_command<T: Command>(&self, cmd: T) {
+        // a cloned sender always has capacity for one message
+        let (tx, _) = oneshot_channel();
+        if let Ok(msg) = CommandMessage::with_session(cmd, tx, Some(self.session_id.clone())) {
+            let _ = self.sender.clone().try_send(TargetMessage::Command(msg));
+        }
+    }
+
     pub(crate) fn sender(&self) -> &Sender<TargetMessage> {
         &self.sender
     }
 
-    /// Returns the first element in the node which matches the given CSS
+    /// Subscribe to all events of this page's target with the given
+    /// identifiers
+    pub(crate) async fn event_listener<I, S>(
+        &self,
+        methods: I,
+    ) -> Result<UnboundedReceiver<CdpEvent>>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'static, str>>,
+    {
+        let (tx, rx) = unbounded();
+        self.sender
+            .clone()
+            .send(TargetMessage::AddEventListener(EventListenerRequest::new(
+                methods, tx,
+            )))
+            .await?;
+        Ok(rx)
+    }
+
+    /// Returns the first element in the node which matches the given
     /// selector.
-    pub async fn find_element(&self, selector: impl Into<String>, node: NodeId) -> Result<NodeId> {
-        Ok(self
-            .execute(QuerySelectorParams::new(node, selector))
-            .await?
-            .node_id)
+    pub async fn find_element(
+        &self,
+        selector: impl Into<Selector>,
+        node: NodeId,
+    ) -> Result<NodeId> {
+        match selector.into() {
+            Selector::Css(selector) => Ok(self
+                .execute(QuerySelectorParams::new(node, selector))
+                .await?
+                .node_id),
+            selector => self
+                .find_elements(selector, node)
+                .await?
+                .into_iter()
+                .next()
+                .ok_or(CdpError::NotFound),
+        }
     }
 
     /// Return all `Element`s inside the node that match the given selector
     pub(crate) async fn find_elements(
         &self,
-        selector: impl Into<String>,
+        selector: impl Into<Selector>,
         node: NodeId,
     ) -> Result<Vec<NodeId>> {
-        Ok(self
-            .execute(QuerySelectorAllParams::new(node, selector))
+        let selector = selector.into();
+        match selector {
+            Selector::Css(selector) => Ok(self
+                .execute(QuerySelectorAllParams::new(node, selector))
+                .await?
+                .result
+                .node_ids),
+            Selector::Role { role, name } => {
+                let mut query = QueryAxTreeParams::builder().node_id(node).role(role);
+                if let Some(name) = name {
+                    query = query.accessible_name(name);
+                }
+                let backend_node_ids: Vec<_> = self
+                    .execute(query.build())
+                    .await?
+                    .result
+                    .nodes
+                    .into_iter()
+                    .filter(|node| {
+                        node.role
+                            .as_ref()
+                            .and_then(|role| role.value.as_ref())
+                            .and_then(|role| role.as_str())
+                            != Some("StaticText")
+                    })
+                    .filter_map(|node| node.backend_dom_node_id)
+                    .collect();
+                if backend_node_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                Ok(self
+                    .execute(PushNodesByBackendIdsToFrontendParams::new(backend_node_ids))
+                    .await?
+                    .result
+                    .node_ids)
+            }
+            selector => {
+                let query_all = selector.query_all_fn().expect("is resolved via javascript");
+                let root = self
+                    .execute(ResolveNodeParams::builder().node_id(node).build())
+                    .await?
+                    .result
+                    .object
+                    .object_id
+                    .ok_or_else(|| CdpError::msg(format!("No object Id found for {:?}", node)))?;
+                let resp = self.call_js_fn(query_all, false, root).await?;
+                if let Some(details) = resp.exception_details {
+                    return Err(CdpError::msg(format!(
+                        "Failed to query `{}`: {}",
+                        selector,
+                        details
+                            .exception
+                            .and_then(|ex| ex.description)
+                            .unwrap_or(details.text)
+                    )));
+                }
+                let array = resp
+                    .result
+                    .object_id
+                    .ok_or_else(|| CdpError::msg(format!("Failed to query `{}`", selector)))?;
+                let node_ids = self.array_node_ids(array.clone()).await;
+                self.execute(ReleaseObjectParams::new(array)).await?;
+                node_ids
+            }
+        }
+    }
+
+    /// Returns the `NodeId`s of all nodes in the remote javascript array
+    async fn array_node_ids(&self, array: RemoteObjectId) -> Result<Vec<NodeId>> {
+        let mut items: Vec<_> = self
+            .execute(
+                GetPropertiesParams::builder()
+                    .object_id(array)
+                    .own_properties(true)
+                    .build()
+                    .unwrap(),
+            )
             .await?
             .result
-            .node_ids)
+            .result
+            .into_iter()
+            .filter_map(|prop| {
+                let idx = prop.name.parse::<usize>().ok()?;
+                Some((idx, prop.value?.object_id?))
+            })
+            .collect();
+        items.sort_by_key(|(idx, _)| *idx);
+
+        let mut node_ids = Vec::with_capacity(items.len());
+        for (_, object_id) in items {
+            node_ids.push(
+                self.execute(RequestNodeParams::new(object_id))
+                    .await?
+                    .result
+                    .node_id,
+            );
+        }
+        Ok(node_ids)
     }
 
     /// Moves the mouse to this point (dispatches a mouseMoved event)
     pub async fn move_mouse_to_point(&self, point: Point) -> Result<&Self> {
-        let cmd = DispatchMouseEventParams::builder()
-            .r#type(DispatchMouseEventType::MouseMoved)
-            .x(point.x)
-            .y(point.y)
-            .build()
-            .unwrap();
-        self.execute(cmd).await?;
-        Ok(self)
+        self.mouse_move(point, 1).await
     }
 
     /// Performs a mouse click event at the point's location
     pub async fn click(&self, point: Point) -> Result<&Self> {
-        let cmd = DispatchMouseEventParams::builder()
-            .x(point.x)
-            .y(point.y)
-            .button(MouseButton::Left)
-            .click_count(1);
+        self.click_with(point, ClickOptions::default()).await
+    }
 
-        self.execute(
-            cmd.clone()
-                .r#type(DispatchMouseEventType::MousePressed)
+    /// Moves the mouse to the point and performs `click_count` clicks with the
+    /// button
+    pub async fn click_with(&self, point: Point, opts: ClickOptions) -> Result<&Self> {
+        self.mouse_move(point, 1).await?;
+        for click_count in 1..=opts.click_count.max(1) {
+            self.mouse_down(opts.button.clone(), click_count).await?;
+            if let Some(delay) = opts.delay {
+                async_std::task::sleep(delay).await;
+            }
+            self.mouse_up(opts.button.clone(), click_count).await?;
+        }
+        Ok(self)
+    }
+
+    /// The coverage that is collected for this page
+    pub(crate) fn coverage_state(&self) -> &CoverageState {
+        &self.coverage
+    }
+
+    /// The scripts the debugger tracks for this page
+    pub(crate) fn debugger_state(&self) -> &DebuggerState {
+        &self.debugger
+    }
+
+    /// A snapshot of the current state of the mouse
+    pub(crate) fn mouse_state(&self) -> MouseState {
+        self.mouse.lock().unwrap().clone()
+    }
+
+    /// Moves the mouse from its current position to the point in `steps`
+    /// `mouseMoved` events
+    pub(crate) async fn mouse_move(&self, point: Point, steps: usize) -> Result<&Self> {
+        let state = self.mouse_state();
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let ratio = step as f64 / steps as f64;
+            let next = Point::new(
+                state.position.x + (point.x - state.position.x) * ratio,
+                state.position.y + (point.y - state.position.y) * ratio,
+            );
+            let cmd = DispatchMouseEventParams::builder()
+                .r#type(DispatchMouseEventType::MouseMoved)
+                .modifiers(self.keyboard_state().modifiers())
+                .x(next.x)
+                .y(next.y)
+                .button(state.moving_button())
+                .buttons(state.buttons)
                 .build()
-                .unwrap(),
+                .unwrap();
+            self.execute(cmd).await?;
+            self.mouse.lock().unwrap().position = next;
+        }
+        Ok(self)
+    }
+
+    /// Presses the button at the current position of the mouse
+    pub(crate) async fn mouse_down(&self, button: MouseButton, click_count: i64) -> Result<&Self> {
+        let state = {
+            let mut state = self.mouse.lock().unwrap();
+            state.buttons |= button_mask(&button);
+            state.clone()
+        };
+        self.dispatch_mouse_button(
+            DispatchMouseEventType::MousePressed,
+            button,
+            click_count,
+            state,
         )
-        .await?;
+        .await
+    }
 
-        self.execute(
-            cmd.r#type(DispatchMouseEventType::MouseReleased)
-                .build()
-                .unwrap(),
+    /// Releases the button at the current position of the mouse
+    pub(crate) async fn mouse_up(&self, button: MouseButton, click_count: i64) -> Result<&Self> {
+        let state = {
+            let mut state = self.mouse.lock().unwrap();
+            state.buttons &= !button_mask(&button);
+            state.clone()
+        };
+        self.dispatch_mouse_button(
+            DispatchMouseEventType::MouseReleased,
+            button,
+            click_count,
+            state,
         )
-        .await?;
+        .await
+    }
+
+    async fn dispatch_mouse_button(
+        &self,
+        r#type: DispatchMouseEventType,
+        button: MouseButton,
+        click_count: i64,
+        state: MouseState,
+    ) -> Result<&Self> {
+        let cmd = DispatchMouseEventParams::builder()
+            .r#type(r#type)
+            .modifiers(self.keyboard_state().modifiers())
+            .x(state.position.x)
+            .y(state.position.y)
+            .button(button)
+            .buttons(state.buttons)
+            .click_count(click_count)
+            .build()
+            .unwrap();
+        self.execute(cmd).await?;
+        Ok(self)
+    }
+
+    /// Dispatches a `mouseWheel` event at the current position of the mouse
+    pub(crate) async fn mouse_wheel(&self, delta_x: f64, delta_y: f64) -> Result<&Self> {
+        let state = self.mouse_state();
+        let cmd = DispatchMouseEventParams::builder()
+            .r#type(DispatchMouseEventType::MouseWheel)
+            .modifiers(self.keyboard_state().modifiers())
+            .x(state.position.x)
+            .y(state.position.y)
+            .buttons(state.buttons)
+            .delta_x(delta_x)
+            .delta_y(delta_y)
+            .build()
+            .unwrap();
+        self.execute(cmd).await?;
+        Ok(self)
+    }
+
+    /// Dispatches a touch event with a touch for each of the points
+    pub(crate) async fn dispatch_touch(
+        &self,
+        r#type: DispatchTouchEventType,
+        points: &[Point],
+    ) -> Result<&Self> {
+        let touch_points = points
+            .iter()
+            .enumerate()
+            .map(|(id, point)| {
+                TouchPoint::builder()
+                    .x(point.x)
+                    .y(point.y)
+                    .id(id as f64)
+                    .build()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        let cmd = DispatchTouchEventParams::builder()
+            .r#type(r#type)
+            .touch_points(touch_points)
+            .modifiers(self.keyboard_state().modifiers())
+            .build()
+            .unwrap();
+        self.execute(cmd).await?;
         Ok(self)
     }
 
@@ -150,49 +542,162 @@ impl PageInner {
     /// char is inserted as a separate keystroke. So sending
     /// `page.type_str("Enter")` will be processed as a series of single
     /// keystrokes:  `["E", "n", "t", "e", "r"]`. To simulate pressing the
-    /// actual Enter key instead use `page.press_key(
-    /// keys::get_key_definition("Enter").unwrap())`.
+    /// actual Enter key instead use `page.press_key("Enter")`.
     pub async fn type_str(&self, input: impl AsRef<str>) -> Result<&Self> {
-        for c in input.as_ref().split("").filter(|s| !s.is_empty()) {
-            self.press_key(c).await?;
+        self.type_str_with_delay(input, None).await
+    }
+
+    /// Types the input with the `delay` between keystrokes, characters that
+    /// are not part of the keyboard layout are inserted as text.
+    pub(crate) async fn type_str_with_delay(
+        &self,
+        input: impl AsRef<str>,
+        delay: Option<Duration>,
+    ) -> Result<&Self> {
+        let mut buf = [0; 4];
+        for c in input.as_ref().chars() {
+            let key = match c {
+                '\n' | '\r' => "Enter",
+                c => c.encode_utf8(&mut buf),
+            };
+            let is_key = self.keyboard_state().layout.key_definition(key).is_some();
+            if is_key {
+                self.key_down(key).await?;
+                if let Some(delay) = delay {
+                    async_std::task::sleep(delay).await;
+                }
+                self.key_up(key).await?;
+            } else {
+                self.insert_text(key).await?;
+            }
+            if let Some(delay) = delay {
+                async_std::task::sleep(delay).await;
+            }
         }
         Ok(self)
     }
 
     /// Uses the `DispatchKeyEvent` mechanism to simulate pressing keyboard
     /// keys.
+    ///
+    /// The `key` can also be a combination of keys separated by `+`, like
+    /// `"Control+KeyA"`
     pub async fn press_key(&self, key: impl AsRef<str>) -> Result<&Self> {
-        let key = key.as_ref();
-        let key_definition = keys::get_key_definition(key)
-            .ok_or_else(|| CdpError::msg(format!("Key not found: {}", key)))?;
-        let mut cmd = DispatchKeyEventParams::builder();
+        self.press_key_with_delay(key, None).await
+    }
+
+    /// Presses the keys of the combination, waiting for the `delay` before
+    /// releasing the last key
+    pub(crate) async fn press_key_with_delay(
+        &self,
+        keys: impl AsRef<str>,
+        delay: Option<Duration>,
+    ) -> Result<&Self> {
+        let keys = split_combo(keys.as_ref());
+        for key in &keys {
+            self.key_down(key).await?;
+        }
+        if let Some(delay) = delay {
+            async_std::task::sleep(delay).await;
+        }
+        for key in keys.iter().rev() {
+            self.key_up(key).await?;
+        }
+        Ok(self)
+    }
+
+    /// Dispatches a `keyDown` event for the key and marks it as pressed
+    pub(crate) async fn key_down(&self, key: &str) -> Result<&Self> {
+        let (key_definition, modifiers, auto_repeat) = {
+            let mut state = self.keyboard_state();
+            let key_definition = key_definition(&*state.layout, key)?;
+            let auto_repeat = state
+                .pressed
+                .insert(
+                    key_definition.code.to_string(),
+                    key_definition.modifier_bit(),
+                )
+                .is_some();
+            (key_definition, state.modifiers(), auto_repeat)
+        };
+        let mut cmd = key_event(&key_definition, modifiers);
 
         // See https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L114-L115
         // And https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L52
-        let key_down_event_type = if let Some(txt) = key_definition.text {
-            cmd = cmd.text(txt);
-            DispatchKeyEventType::KeyDown
-        } else if key_definition.key.len() == 1 {
-            cmd = cmd.text(key_definition.key);
+        // Text is only inserted if no modifier besides shift is pressed, so
+        // that shortcuts like `Control+KeyA` are not typed
+        let text = key_definition
+            .text
+            .or_else(|| Some(key_definition.key).filter(|key| key.chars().count() == 1))
+            .filter(|_| modifiers & !SHIFT_MODIFIER == 0);
+        let key_down_event_type = if let Some(txt) = text {
+            cmd = cmd.text(txt).unmodified_text(txt);
             DispatchKeyEventType::KeyDown
         } else {
             DispatchKeyEventType::RawKeyDown
         };
 
-        cmd = cmd
-            .r#type(DispatchKeyEventType::KeyDown)
-            .key(key_definition.key)
-            .code(key_definition.code)
-            .windows_virtual_key_code(key_definition.key_code)
-            .native_virtual_key_code(key_definition.key_code);
+        self.execute(
+            cmd.r#type(key_down_event_type)
+                .auto_repeat(auto_repeat)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+        Ok(self)
+    }
+
+    /// Dispatches a `keyUp` event for the key and marks it as released
+    pub(crate) async fn key_up(&self, key: &str) -> Result<&Self> {
+        let (key_definition, modifiers) = {
+            let mut state = self.keyboard_state();
+            let key_definition = key_definition(&*state.layout, key)?;
+            state.pressed.remove(key_definition.code);
+            (key_definition, state.modifiers())
+        };
+        self.execute(
+            key_event(&key_definition, modifiers)
+                .r#type(DispatchKeyEventType::KeyUp)
+                .build()
+                .unwrap(),
+        )
+        .await?;
+        Ok(self)
+    }
+
+    /// Inserts the text without dispatching key events
+    pub(crate) async fn insert_text(&self, text: impl Into<String>) -> Result<&Self> {
+        self.execute(InsertTextParams::new(text)).await?;
+        Ok(self)
+    }
 
-        self.execute(cmd.clone().r#type(key_down_event_type).build().unwrap())
-            .await?;
-        self.execute(cmd.r#type(DispatchKeyEventType::KeyUp).build().unwrap())
-            .await?;
+    /// Intercepts file choosers as long as at least one `FileChoosers`
+    /// stream exists
+    pub(crate) async fn intercept_file_choosers(&self) -> Result<&Self> {
+        self.file_chooser_streams.fetch_add(1, Ordering::SeqCst);
+        if let Err(err) = self
+            .execute(SetInterceptFileChooserDialogParams::new(true))
+            .await
+        {
+            self.release_file_choosers();
+            return Err(err);
+        }
         Ok(self)
     }
 
+    /// Stops intercepting file choosers once the last `FileChoosers` stream
+    /// is dropped
+    pub(crate) fn release_file_choosers(&self) {
+        if self.file_chooser_streams.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.send_command(SetInterceptFileChooserDialogParams::new(false));
+        }
+    }
+
+    /// The keyboard of the page
+    pub(crate) fn keyboard_state(&self) -> MutexGuard<'_, KeyboardState> {
+        self.keyboard.lock().unwrap()
+    }
+
     /// Calls function with given declaration on the remote object with the
     /// matching id
     pub async fn call_js_fn(
@@ -214,6 +719,108 @@ impl PageInner {
             .await?;
         Ok(resp.result)
     }
+
+    /// Calls the async `function_declaration` with `this` bound to the `root`
+    /// object, or the page's `document` if `None`, until it resolves.
+    ///
+    /// If the function resolves to `undefined`, or does not resolve within the
+    /// `timeout`, a `CdpError::WaitTimeout` with the `waiting_for`
+    /// description is returned. Attempts that fail because a navigation
+    /// destroyed the execution context are retried for the `document`.
+    pub(crate) async fn wait_for(
+        &self,
+        function_declaration: String,
+        root: Option<RemoteObjectId>,
+        waiting_for: impl FnOnce() -> String,
+        timeout: Duration,
+    ) -> Result<RemoteObject> {
+        let retry = root.is_none();
+        let wait = async {
+            loop {
+                let res = if let Some(root) = root.clone() {
+                    self.call_js_fn(function_declaration.clone(), true, root)
+                        .await
+                        .map(|resp| (resp.result, resp.exception_details))
+                } else {
+                    self.execute(
+                        EvaluateParams::builder()
+                            .expression(format!("({}).call(document)", function_declaration))
+                            .await_promise(true)
+                            .build()
+                            .unwrap(),
+                    )
+                    .await
+                    .map(|resp| (resp.result.result, resp.result.exception_details))
+                };
+                match res {
+                    Ok((_, Some(details))) => {
+                        if retry && is_context_destroyed(&details.text) {
+                            continue;
+                        }
+                        let msg = details
+                            .exception
+                            .and_then(|ex| ex.description)
+                            .unwrap_or(details.text);
+                        return Err(CdpError::ChromeMessage(msg));
+                    }
+                    Ok((obj, None)) => return Ok(obj),
+                    Err(CdpError::Chrome(err)) if retry && is_context_destroyed(&err.message) => {
+                        continue
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        };
+        match async_std::future::timeout(timeout, wait).await {
+            Ok(Ok(obj)) if obj.r#type != RemoteObjectType::Undefined => Ok(obj),
+            Ok(Err(err)) => Err(err),
+            _ => Err(CdpError::WaitTimeout {
+                waiting_for: waiting_for(),
+                timeout,
+            }),
+        }
+    }
+}
+
+/// The `modifiers` bit of the shift key
+const SHIFT_MODIFIER: i64 = 8;
+
+/// Resolves the key in the layout
+fn key_definition(layout: &dyn KeyboardLayout, key: &str) -> Result<KeyDefinition> {
+    layout
+        .key_definition(key)
+        .cloned()
+        .ok_or_else(|| CdpError::msg(format!("Key not found: {}", key)))
+}
+
+/// The key event for the key with all fields set except `type` and `text`
+fn key_event(key_definition: &KeyDefinition, modifiers: i64) -> DispatchKeyEventParamsBuilder {
+    let location = key_definition.location();
+    DispatchKeyEventParams::builder()
+        .modifiers(modifiers)
+        .key(key_definition.key)
+        .code(key_definition.code)
+        .windows_virtual_key_code(key_definition.key_code)
+        .native_virtual_key_code(key_definition.key_code)
+        .location(location)
+        .is_keypad(location == 3)
+}
+
+/// Whether the error message indicates that the execution context was
+/// destroyed, e.g. due to a navigation
+fn is_context_destroyed(msg: &str) -> bool {
+    msg.contains("Execution context was destroyed") || msg.contains("Cannot find context")
+}
+
+impl IoExecutor for PageInner {
+    fn read(&self, params: ReadParams) -> BoxFuture<'static, Result<ReadReturns>> {
+        let resp = execute(params, self.sender.clone(), Some(self.session_id.clone()));
+        Box::pin(async move { Ok(resp.await?.result) })
+    }
+
+    fn close(&self, params: CloseParams) {
+        self.send_command(params)
+    }
 }
 
 pub(crate) async fn execute<T: Command>(
@@ -11,21 +11,25 @@ use chromiumoxide_types::{Command, Method, Request, Response};
 
 use crate::cmd::CommandChain;
 use crate::cmd::CommandMessage;
+use crate::dialog::DialogPolicy;
 use crate::error::{CdpError, DeadlineExceeded, Result};
-use crate::handler::emulation::EmulationManager;
+use crate::handler::emulation::{EmulationManager, EmulationOverride};
 use crate::handler::frame::FrameNavigationRequest;
 use crate::handler::frame::{
     FrameEvent, FrameManager, NavigationError, NavigationId, NavigationOk,
 };
-use crate::handler::network::NetworkManager;
+use crate::handler::network::{HttpResponse, NetworkManager, NetworkOverride};
 use crate::handler::page::PageHandle;
 use crate::handler::viewport::Viewport;
-use crate::handler::PageInner;
+use crate::handler::{HandlerConfig, PageInner};
+use crate::listeners::{EventListenerRequest, EventListeners};
 use crate::page::Page;
-use chromiumoxide_cdp::cdp::browser_protocol::page::{FrameId, GetFrameTreeParams};
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    EventJavascriptDialogOpening, FrameId, GetFrameTreeParams,
+};
 use chromiumoxide_cdp::cdp::browser_protocol::{
     browser::BrowserContextId,
-    log as cdplog, performance,
+    inspector, log as cdplog, performance,
     target::{AttachToTargetParams, SessionId, SetAutoAttachParams, TargetId, TargetInfo},
 };
 use chromiumoxide_cdp::cdp::events::CdpEvent;
@@ -63,7 +67,8 @@ pub struct Target {
     frame_manager: FrameManager,
     network_manager: NetworkManager,
     emulation_manager: EmulationManager,
-    viewport: Viewport,
+    /// The viewport the page is emulated with, `None` for the window size
+    viewport: Option<Viewport>,
     /// The identifier of the session this target is attached to
     session_id: Option<SessionId>,
     /// The handle of the browser page of this target
@@ -74,8 +79,15 @@ pub struct Target {
     queued_events: VecDeque<TargetEvent>,
     /// Senders that need to be notified once the main frame has loaded
     wait_until_frame_loaded: Vec<Sender<Result<String>>>,
+    /// Senders that need to be notified once the main frame fired the
+    /// lifecycle event
+    wait_for_lifecycle_event: Vec<(&'static str, Sender<()>)>,
     /// The sender who requested the page.
     initiator: Option<Sender<Result<Page>>>,
+    /// Subscribers to events of this target
+    event_listeners: EventListeners,
+    /// How to respond to dialogs nobody listens for
+    dialog_policy: Option<DialogPolicy>,
     /// Used to tracked whether this target should initialize its state
     initialize: bool,
 }
@@ -83,20 +95,23 @@ pub struct Target {
 impl Target {
     /// Create a new target instance with `TargetInfo` after a
     /// `CreateTargetParams` request.
-    pub fn new(info: TargetInfo) -> Self {
+    pub fn new(info: TargetInfo, config: &HandlerConfig) -> Self {
         Self {
             info,
             is_closed: false,
             frame_manager: Default::default(),
             network_manager: Default::default(),
             emulation_manager: Default::default(),
-            viewport: Default::default(),
+            viewport: config.viewport.clone(),
             session_id: None,
             page: None,
             init_state: TargetInit::AttachToTarget,
             wait_until_frame_loaded: Default::default(),
+            wait_for_lifecycle_event: Default::default(),
             queued_events: Default::default(),
             initiator: None,
+            event_listeners: Default::default(),
+            dialog_policy: config.dialog_policy,
             initialize: false,
         }
     }
@@ -183,12 +198,16 @@ impl Target {
     }
 
     pub fn on_event(&mut self, event: CdpEventMessage) {
+        self.event_listeners.start_send(&event);
         match event.params {
             // `FrameManager` events
             CdpEvent::PageFrameAttached(ev) => self
                 .frame_manager
                 .on_frame_attached(ev.frame_id.clone(), Some(ev.parent_frame_id)),
-            CdpEvent::PageFrameDetached(ev) => self.frame_manager.on_frame_detached(&ev),
+            CdpEvent::PageFrameDetached(ev) => {
+                self.network_manager.on_frame_detached(&ev.frame_id);
+                self.frame_manager.on_frame_detached(&ev)
+            }
             CdpEvent::PageFrameNavigated(ev) => self.frame_manager.on_frame_navigated(ev.frame),
             CdpEvent::PageNavigatedWithinDocument(ev) => {
                 self.frame_manager.on_frame_navigated_within_document(&ev)
@@ -225,10 +244,57 @@ impl Target {
             CdpEvent::NetworkLoadingFailed(ev) => {
                 self.network_manager.on_network_loading_failed(&ev)
             }
+            CdpEvent::PageJavascriptDialogOpening(ev) => self.on_javascript_dialog_opening(&ev),
+            CdpEvent::InspectorTargetReloadedAfterCrash(_) => self.reapply_overrides(),
             _ => {}
         }
     }
 
+    /// Responds to the dialog according to the `DialogPolicy` if there is no
+    /// listener that takes care of it.
+    fn on_javascript_dialog_opening(&mut self, event: &EventJavascriptDialogOpening) {
+        if self
+            .event_listeners
+            .has_listener(EventJavascriptDialogOpening::IDENTIFIER)
+        {
+            return;
+        }
+        if let Some(policy) = self.dialog_policy {
+            let params = policy.handle_dialog(event);
+            self.queued_events.push_back(TargetEvent::Request(Request {
+                method: params.identifier(),
+                session_id: self.session_id.clone().map(Into::into),
+                params: serde_json::to_value(params).unwrap(),
+            }));
+        }
+    }
+
+    /// Applies the viewport and all emulation and network overrides again,
+    /// after the page lost them by crashing
+    fn reapply_overrides(&mut self) {
+        let mut cmds = Vec::new();
+        if let Some(viewport) = self.viewport.as_ref() {
+            let (set_device, set_touch) = EmulationManager::viewport_commands(viewport);
+            cmds.push((
+                set_device.identifier(),
+                serde_json::to_value(set_device).unwrap(),
+            ));
+            cmds.push((
+                set_touch.identifier(),
+                serde_json::to_value(set_touch).unwrap(),
+            ));
+        }
+        cmds.extend(self.emulation_manager.override_commands());
+        cmds.extend(self.network_manager.override_commands());
+        for (method, params) in cmds {
+            self.queued_events.push_back(TargetEvent::Request(Request {
+                method,
+                session_id: self.session_id.clone().map(Into::into),
+                params,
+            }));
+        }
+    }
+
     /// Advance that target's state
     pub(crate) fn poll(&mut self, cx: &mut Context<'_>, now: Instant) -> Option<TargetEvent> {
         if !self.initialize {
@@ -274,7 +340,7 @@ impl Target {
                     now,
                     cmds,
                     TargetInit::InitializingEmulation(
-                        self.emulation_manager.init_commands(&self.viewport),
+                        self.emulation_manager.init_commands(self.viewport.as_ref()),
                     )
                 );
             }
@@ -308,6 +374,13 @@ impl Target {
                         let _ = tx.send(frame.url.clone().ok_or(CdpError::NotFound));
                     }
                 }
+                let (fired, pending) = std::mem::take(&mut self.wait_for_lifecycle_event)
+                    .into_iter()
+                    .partition(|(event, _)| frame.has_lifecycle_event(event));
+                self.wait_for_lifecycle_event = pending;
+                for (_, tx) in fired {
+                    let _ = tx.send(());
+                }
             }
 
             // Drain queued messages first.
@@ -328,6 +401,15 @@ impl Target {
                             let _ = tx
                                 .send(self.frame_manager.main_frame().and_then(|f| f.url.clone()));
                         }
+                        TargetMessage::MainResponse(tx) => {
+                            let network_manager = &self.network_manager;
+                            let resp = self.frame_manager.main_frame().and_then(|frame| {
+                                network_manager
+                                    .document_response(&frame.id, frame.loader_id.as_ref()?)
+                                    .cloned()
+                            });
+                            let _ = tx.send(resp);
+                        }
                         TargetMessage::WaitForNavigation(tx) => {
                             if let Some(frame) = self.frame_manager.main_frame() {
                                 if frame.is_loaded() {
@@ -339,6 +421,41 @@ impl Target {
                                 self.wait_until_frame_loaded.push(tx);
                             }
                         }
+                        TargetMessage::WaitForLifecycleEvent(event, tx) => {
+                            if self
+                                .frame_manager
+                                .main_frame()
+                                .map(|frame| frame.has_lifecycle_event(event))
+                                .unwrap_or_default()
+                            {
+                                let _ = tx.send(());
+                            } else {
+                                self.wait_for_lifecycle_event.push((event, tx));
+                            }
+                        }
+                        TargetMessage::ClearLifecycleEvents => {
+                            self.frame_manager.clear_main_frame_lifecycle_events();
+                        }
+                        TargetMessage::AddEventListener(req) => {
+                            self.event_listeners.add_listener(req);
+                        }
+                        TargetMessage::Network(network, tx) => {
+                            let _ = tx.send(self.network_manager.resolve_override(network));
+                        }
+                        TargetMessage::NetworkApplied(network) => {
+                            self.network_manager.track_override(network);
+                        }
+                        TargetMessage::Emulate(emulation, tx) => {
+                            let _ = tx.send(self.emulation_manager.resolve_override(emulation));
+                        }
+                        TargetMessage::EmulationApplied(emulation) => {
+                            self.emulation_manager.track_override(emulation);
+                        }
+                        TargetMessage::ViewportChanged(viewport, tx) => {
+                            let needs_reload = self.emulation_manager.update_viewport(&viewport);
+                            self.viewport = Some(viewport);
+                            let _ = tx.send(needs_reload);
+                        }
                     }
                 }
             }
@@ -384,6 +501,7 @@ impl Target {
             .unwrap();
         let enable_performance = performance::EnableParams::default();
         let enable_log = cdplog::EnableParams::default();
+        let enable_inspector = inspector::EnableParams::default();
         CommandChain::new(vec![
             (attach.identifier(), serde_json::to_value(attach).unwrap()),
             (
@@ -394,6 +512,10 @@ impl Target {
                 enable_log.identifier(),
                 serde_json::to_value(enable_log).unwrap(),
             ),
+            (
+                enable_inspector.identifier(),
+                serde_json::to_value(enable_inspector).unwrap(),
+            ),
         ])
     }
 }
@@ -444,6 +566,31 @@ pub(crate) enum TargetMessage {
     MainFrame(Sender<Option<FrameId>>),
     /// Return the url of this target's page
     Url(Sender<Option<String>>),
+    /// Return the response of the document the main frame currently shows
+    MainResponse(Sender<Option<HttpResponse>>),
     /// A Message that resolves when the frame finished loading a new url
     WaitForNavigation(Sender<Result<String>>),
+    /// A Message that resolves once the main frame fired the lifecycle event,
+    /// like `DOMContentLoaded`, for its current document
+    WaitForLifecycleEvent(&'static str, Sender<()>),
+    /// Forget the lifecycle events the main frame fired for its current
+    /// document, before a command replaces the document
+    ClearLifecycleEvents,
+    /// Subscribe to events of this target
+    AddEventListener(EventListenerRequest),
+    /// Resolve the network override against the tracked ones, responds with
+    /// the command to apply it
+    Network(NetworkOverride, Sender<NetworkOverride>),
+    /// The command of a network override was applied successfully and is
+    /// tracked from now on
+    NetworkApplied(NetworkOverride),
+    /// Resolve the emulation override against the tracked ones, responds
+    /// with the command to apply it
+    Emulate(EmulationOverride, Sender<EmulationOverride>),
+    /// The command of an emulation override was applied successfully and is
+    /// tracked from now on
+    EmulationApplied(EmulationOverride),
+    /// The viewport of the page was changed, responds with whether the page
+    /// needs to be reloaded
+    ViewportChanged(Viewport, Sender<bool>),
 }
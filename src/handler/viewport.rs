@@ -1,10 +1,18 @@
-#[derive(Debug, Clone)]
+/// The dimensions and capabilities of the screen emulated for a page.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Viewport {
+    /// The width of the page in CSS pixels
     pub width: u32,
+    /// The height of the page in CSS pixels
     pub height: u32,
+    /// The ratio of device pixels to CSS pixels, defaults to 1.0
     pub device_scale_factor: Option<f64>,
+    /// Whether to emulate a mobile device, taking the `meta viewport` tag into
+    /// account
     pub is_mobile: bool,
+    /// Whether the screen is in landscape orientation
     pub is_landscape: bool,
+    /// Whether to emulate a touch screen
     pub has_touch: bool,
 }
 
@@ -0,0 +1,311 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use futures::AsyncRead;
+
+use chromiumoxide_cdp::cdp::browser_protocol::io::{
+    CloseParams, ReadParams, ReadReturns, StreamHandle,
+};
+
+use crate::error::Result;
+
+/// Executes the `IO` commands of an [`IoStream`]
+pub trait IoExecutor: fmt::Debug + Send + Sync {
+    /// Reads the next chunk of the stream
+    fn read(&self, params: ReadParams) -> BoxFuture<'static, Result<ReadReturns>>;
+
+    /// Closes the stream.
+    ///
+    /// This is also called when an unfinished `IoStream` is dropped, so it
+    /// must not wait for the response.
+    fn close(&self, params: CloseParams);
+}
+
+/// Reads a stream of the browser, like a pdf printed with
+/// `transferMode: ReturnAsStream`, chunk by chunk via `IO.read`.
+///
+/// Chunks that are base64 encoded are decoded transparently. The stream is
+/// closed via `IO.close` once it is read to the end or dropped.
+///
+/// # Example read the body of a response paused via the `Fetch` domain
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::cdp::browser_protocol::fetch::{RequestId, TakeResponseBodyAsStreamParams};
+/// # use futures::AsyncReadExt;
+/// # async fn demo(page: Page, request_id: RequestId) -> Result<()> {
+///     let handle = page
+///         .execute(TakeResponseBodyAsStreamParams::new(request_id))
+///         .await?
+///         .result
+///         .stream;
+///     let mut body = Vec::new();
+///     page.read_stream(handle).read_to_end(&mut body).await?;
+///     # Ok(())
+/// # }
+/// ```
+pub struct IoStream {
+    executor: Arc<dyn IoExecutor>,
+    handle: StreamHandle,
+    /// The maximum number of bytes to read per `IO.read`
+    chunk_size: Option<i64>,
+    /// The `IO.read` request in flight
+    pending: Option<BoxFuture<'static, Result<ReadReturns>>>,
+    /// The last chunk that was read
+    buffer: Vec<u8>,
+    /// How much of the buffer was already read
+    pos: usize,
+    /// Whether the end of the stream was reached
+    eof: bool,
+    /// Whether the handle was closed
+    closed: bool,
+}
+
+impl IoStream {
+    pub fn new(executor: Arc<dyn IoExecutor>, handle: StreamHandle) -> Self {
+        Self {
+            executor,
+            handle,
+            chunk_size: None,
+            pending: None,
+            buffer: Vec::new(),
+            pos: 0,
+            eof: false,
+            closed: false,
+        }
+    }
+
+    /// Read at most `chunk_size` bytes per `IO.read`, chromium's default is
+    /// used otherwise.
+    pub fn chunk_size(mut self, chunk_size: i64) -> Self {
+        self.chunk_size = Some(chunk_size);
+        self
+    }
+
+    pub fn handle(&self) -> &StreamHandle {
+        &self.handle
+    }
+
+    /// Closes the handle, if not done already
+    fn close(&mut self) {
+        if !self.closed {
+            self.closed = true;
+            self.executor.close(CloseParams::new(self.handle.clone()));
+        }
+    }
+
+    fn read_params(&self) -> ReadParams {
+        let mut params = ReadParams::new(self.handle.clone());
+        params.size = self.chunk_size;
+        params
+    }
+}
+
+/// Decodes the data of a chunk
+fn chunk_data(chunk: ReadReturns) -> Result<Vec<u8>> {
+    if chunk.base64_encoded.unwrap_or_default() {
+        Ok(base64::decode(&chunk.data)?)
+    } else {
+        Ok(chunk.data.into_bytes())
+    }
+}
+
+impl AsyncRead for IoStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        loop {
+            if this.pos < this.buffer.len() {
+                let n = buf.len().min(this.buffer.len() - this.pos);
+                buf[..n].copy_from_slice(&this.buffer[this.pos..this.pos + n]);
+                this.pos += n;
+                return Poll::Ready(Ok(n));
+            }
+            if this.eof || buf.is_empty() {
+                return Poll::Ready(Ok(0));
+            }
+            if this.pending.is_none() {
+                this.pending = Some(this.executor.read(this.read_params()));
+            }
+            let chunk = match this.pending.as_mut().unwrap().as_mut().poll(cx) {
+                Poll::Ready(chunk) => chunk,
+                Poll::Pending => return Poll::Pending,
+            };
+            this.pending = None;
+            let res = chunk.and_then(|chunk| {
+                let eof = chunk.eof;
+                Ok((chunk_data(chunk)?, eof))
+            });
+            match res {
+                Ok((data, eof)) => {
+                    this.buffer = data;
+                    this.pos = 0;
+                    if eof {
+                        this.eof = true;
+                        this.close();
+                    }
+                }
+                Err(err) => return Poll::Ready(Err(std::io::Error::other(err))),
+            }
+        }
+    }
+}
+
+impl Drop for IoStream {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+impl fmt::Debug for IoStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IoStream")
+            .field("handle", &self.handle)
+            .field("chunk_size", &self.chunk_size)
+            .field("eof", &self.eof)
+            .field("closed", &self.closed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    use futures::executor::block_on;
+    use futures::{future, AsyncReadExt, FutureExt};
+
+    use super::*;
+    use crate::error::CdpError;
+
+    #[derive(Debug, Default)]
+    struct MockExecutor {
+        chunks: Mutex<VecDeque<Result<ReadReturns>>>,
+        reads: Mutex<Vec<ReadParams>>,
+        closed: Mutex<Vec<StreamHandle>>,
+    }
+
+    impl MockExecutor {
+        fn new(chunks: Vec<Result<ReadReturns>>) -> Arc<Self> {
+            Arc::new(Self {
+                chunks: Mutex::new(chunks.into()),
+                ..Default::default()
+            })
+        }
+
+        fn closed(&self) -> Vec<StreamHandle> {
+            self.closed.lock().unwrap().clone()
+        }
+    }
+
+    impl IoExecutor for MockExecutor {
+        fn read(&self, params: ReadParams) -> BoxFuture<'static, Result<ReadReturns>> {
+            self.reads.lock().unwrap().push(params);
+            let chunk = self
+                .chunks
+                .lock()
+                .unwrap()
+                .pop_front()
+                .expect("read past the end");
+            future::ready(chunk).boxed()
+        }
+
+        fn close(&self, params: CloseParams) {
+            self.closed.lock().unwrap().push(params.handle);
+        }
+    }
+
+    fn chunk(data: &str, base64_encoded: bool, eof: bool) -> Result<ReadReturns> {
+        Ok(ReadReturns {
+            base64_encoded: Some(base64_encoded),
+            data: data.to_string(),
+            eof,
+        })
+    }
+
+    fn stream(executor: &Arc<MockExecutor>) -> IoStream {
+        let executor: Arc<dyn IoExecutor> = executor.clone();
+        IoStream::new(executor, StreamHandle::from("1".to_string()))
+    }
+
+    #[test]
+    fn reads_and_decodes_all_chunks() {
+        let executor = MockExecutor::new(vec![
+            chunk("hello ", false, false),
+            chunk(&base64::encode("base64 "), true, false),
+            chunk("world", false, true),
+        ]);
+        let mut data = String::new();
+        block_on(stream(&executor).read_to_string(&mut data)).unwrap();
+        assert_eq!(data, "hello base64 world");
+        assert_eq!(executor.reads.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn reads_chunks_larger_than_the_buffer() {
+        let executor = MockExecutor::new(vec![chunk("abcdef", false, true)]);
+        let mut stream = stream(&executor);
+        let mut buf = [0; 4];
+        assert_eq!(block_on(stream.read(&mut buf)).unwrap(), 4);
+        assert_eq!(&buf, b"abcd");
+        assert_eq!(block_on(stream.read(&mut buf)).unwrap(), 2);
+        assert_eq!(&buf[..2], b"ef");
+        assert_eq!(block_on(stream.read(&mut buf)).unwrap(), 0);
+    }
+
+    #[test]
+    fn requests_the_chunk_size() {
+        let executor = MockExecutor::new(vec![chunk("", false, true)]);
+        let mut data = Vec::new();
+        block_on(stream(&executor).chunk_size(1024).read_to_end(&mut data)).unwrap();
+        assert_eq!(executor.reads.lock().unwrap()[0].size, Some(1024));
+    }
+
+    #[test]
+    fn closes_once_at_the_end() {
+        let executor = MockExecutor::new(vec![chunk("data", false, true)]);
+        let mut stream = stream(&executor);
+        let mut data = Vec::new();
+        block_on(stream.read_to_end(&mut data)).unwrap();
+        assert_eq!(executor.closed().len(), 1);
+        drop(stream);
+        assert_eq!(executor.closed(), vec![StreamHandle::from("1".to_string())]);
+    }
+
+    #[test]
+    fn closes_unfinished_stream_on_drop() {
+        let executor = MockExecutor::new(vec![chunk("data", false, false)]);
+        let mut stream = stream(&executor);
+        let mut buf = [0; 4];
+        block_on(stream.read(&mut buf)).unwrap();
+        assert!(executor.closed().is_empty());
+        drop(stream);
+        assert_eq!(executor.closed().len(), 1);
+    }
+
+    #[test]
+    fn fails_on_read_errors() {
+        let executor = MockExecutor::new(vec![
+            chunk("data", false, false),
+            Err(CdpError::msg("Invalid stream handle")),
+        ]);
+        let mut data = Vec::new();
+        let err = block_on(stream(&executor).read_to_end(&mut data)).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid stream handle");
+    }
+
+    #[test]
+    fn fails_on_invalid_base64() {
+        let executor = MockExecutor::new(vec![chunk("not base64!", true, true)]);
+        let mut data = Vec::new();
+        assert!(block_on(stream(&executor).read_to_end(&mut data)).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use chromiumoxide_cdp::cdp::js_protocol::runtime::{
+    CallFunctionOnParams, ReleaseObjectParams, RemoteObject, RemoteObjectId,
+};
+
+use crate::error::{CdpError, Result};
+use crate::handler::PageInner;
+
+/// Represents an in-page javascript object.
+///
+/// Objects that are not primitive values are kept alive in the page until the
+/// handle is disposed via `JsHandle::dispose`.
+#[derive(Debug, Clone)]
+pub struct JsHandle {
+    remote_object: RemoteObject,
+    tab: Arc<PageInner>,
+}
+
+impl JsHandle {
+    pub(crate) fn new(tab: Arc<PageInner>, remote_object: RemoteObject) -> Self {
+        Self { remote_object, tab }
+    }
+
+    /// The remote object this handle points to
+    pub fn remote_object(&self) -> &RemoteObject {
+        &self.remote_object
+    }
+
+    /// The unique identifier of the object, `None` for primitive values
+    pub fn object_id(&self) -> Option<&RemoteObjectId> {
+        self.remote_object.object_id.as_ref()
+    }
+
+    /// The value of the object if it is a primitive value or was returned by
+    /// value.
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        self.remote_object.value.as_ref()
+    }
+
+    /// Returns a short, human readable representation of the object, like
+    /// chrome would render it in its console.
+    pub fn description(&self) -> String {
+        let obj = &self.remote_object;
+        if let Some(value) = obj.value.as_ref() {
+            if let Some(s) = value.as_str() {
+                return s.to_string();
+            }
+            return value.to_string();
+        }
+        if let Some(value) = obj.unserializable_value.as_ref() {
+            return value.inner().clone();
+        }
+        if let Some(desc) = obj.description.as_ref() {
+            return desc.clone();
+        }
+        obj.r#type.as_ref().to_string()
+    }
+
+    /// Returns a JSON representation of the object.
+    ///
+    /// This fails if the object is not serializable, e.g. it references itself.
+    pub async fn json_value(&self) -> Result<serde_json::Value> {
+        let object_id = if let Some(id) = self.object_id() {
+            id.clone()
+        } else {
+            return Ok(self.value().cloned().unwrap_or(serde_json::Value::Null));
+        };
+        let resp = self
+            .tab
+            .execute(
+                CallFunctionOnParams::builder()
+                    .object_id(object_id)
+                    .function_declaration("function() { return this; }")
+                    .return_by_value(true)
+                    .await_promise(true)
+                    .build()
+                    .unwrap(),
+            )
+            .await?;
+        if let Some(details) = resp.result.exception_details {
+            return Err(CdpError::msg(details.text));
+        }
+        Ok(resp.result.result.value.unwrap_or(serde_json::Value::Null))
+    }
+
+    /// Releases the object in the page, after which the handle can no longer
+    /// be used to refer to it.
+    pub async fn dispose(self) -> Result<()> {
+        if let Some(object_id) = self.remote_object.object_id {
+            self.tab
+                .execute(ReleaseObjectParams::new(object_id))
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::HashMap;
+use std::iter;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::Result;
+use crate::handler::PageInner;
+use crate::keys::{KeyboardLayout, UsKeyboardLayout};
+
+/// Dispatches keyboard events to a page.
+///
+/// Keys are looked up by name (`"a"`, `"Enter"`) or code (`"KeyA"`,
+/// `"ShiftLeft"`) in the page's [`KeyboardLayout`], US by default. Modifier
+/// keys that are held down via [`Keyboard::down`] apply to all following key
+/// and mouse events until they are released.
+///
+/// # Example select all text of the focused input and replace it
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # async fn demo(page: Page) -> Result<()> {
+///     let keyboard = page.keyboard();
+///     keyboard.press("Control+KeyA").await?;
+///     keyboard.type_str("replaced ✓").await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Keyboard {
+    tab: Arc<PageInner>,
+    delay: Option<Duration>,
+}
+
+impl Keyboard {
+    pub(crate) fn new(tab: Arc<PageInner>) -> Self {
+        Self { tab, delay: None }
+    }
+
+    /// Wait for the `delay` between pressing and releasing a key and between
+    /// consecutive keystrokes.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Use the layout to resolve keys for all keyboards of this page, like
+    /// [`DeKeyboardLayout`](crate::keys::DeKeyboardLayout) or a
+    /// [`CustomKeyboardLayout`](crate::keys::CustomKeyboardLayout)
+    pub fn set_layout(&self, layout: Arc<dyn KeyboardLayout>) -> &Self {
+        self.tab.keyboard_state().layout = layout;
+        self
+    }
+
+    /// The bitmask of the modifiers that are currently held down: Alt=1,
+    /// Ctrl=2, Meta/Command=4, Shift=8
+    pub fn modifiers(&self) -> i64 {
+        self.tab.keyboard_state().modifiers()
+    }
+
+    /// Presses the key without releasing it.
+    ///
+    /// If the key is a modifier, it is applied to all following events until
+    /// it is released with `up`.
+    pub async fn down(&self, key: impl AsRef<str>) -> Result<&Self> {
+        self.tab.key_down(key.as_ref()).await?;
+        Ok(self)
+    }
+
+    /// Releases the key
+    pub async fn up(&self, key: impl AsRef<str>) -> Result<&Self> {
+        self.tab.key_up(key.as_ref()).await?;
+        Ok(self)
+    }
+
+    /// Presses and releases the key, or a combination of keys separated by
+    /// `+`, like `"Shift+Tab"` or `"Control+Shift+KeyP"`.
+    ///
+    /// All but the last key of a combination are held down while the last key
+    /// is pressed, and released afterwards in reverse order.
+    pub async fn press(&self, keys: impl AsRef<str>) -> Result<&Self> {
+        self.tab.press_key_with_delay(keys, self.delay).await?;
+        Ok(self)
+    }
+
+    /// Types the text, one keystroke per character.
+    ///
+    /// Characters that are not part of the keyboard layout, like `é` or emoji,
+    /// are inserted via `Input.insertText` instead.
+    pub async fn type_str(&self, text: impl AsRef<str>) -> Result<&Self> {
+        self.tab.type_str_with_delay(text, self.delay).await?;
+        Ok(self)
+    }
+
+    /// Inserts the text without dispatching any key events, similar to
+    /// pasting or an IME.
+    pub async fn insert_text(&self, text: impl Into<String>) -> Result<&Self> {
+        self.tab.insert_text(text).await?;
+        Ok(self)
+    }
+}
+
+/// The tracked state of the keyboard of a page
+#[derive(Debug, Clone)]
+pub(crate) struct KeyboardState {
+    /// The codes of all keys that are held down, with their modifier bit
+    pub pressed: HashMap<String, i64>,
+    /// How keys are resolved
+    pub layout: Arc<dyn KeyboardLayout>,
+}
+
+impl KeyboardState {
+    /// Bitmask of the modifiers that are held down.
+    ///
+    /// A modifier stays active as long as any of its keys is held, like
+    /// `ShiftLeft` after `ShiftRight` was released.
+    pub fn modifiers(&self) -> i64 {
+        self.pressed
+            .values()
+            .fold(0, |modifiers, bit| modifiers | bit)
+    }
+}
+
+impl Default for KeyboardState {
+    fn default() -> Self {
+        Self {
+            pressed: Default::default(),
+            layout: Arc::new(UsKeyboardLayout),
+        }
+    }
+}
+
+/// Splits a combination like `"Control+Shift+KeyP"` into its keys.
+///
+/// A `+` as last key must be doubled, like `"Shift++"`.
+pub(crate) fn split_combo(combo: &str) -> Vec<&str> {
+    let (modifiers, key) = if combo == "+" {
+        ("", "+")
+    } else if let Some(modifiers) = combo.strip_suffix("++") {
+        (modifiers, "+")
+    } else if let Some(idx) = combo.rfind('+') {
+        (&combo[..idx], &combo[idx + 1..])
+    } else {
+        ("", combo)
+    };
+    modifiers
+        .split('+')
+        .filter(|key| !key.is_empty())
+        .chain(iter::once(key))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn modifiers_stay_active_while_any_key_is_held() {
+        let mut state = KeyboardState::default();
+        state.pressed.insert("ShiftLeft".to_string(), 8);
+        state.pressed.insert("ShiftRight".to_string(), 8);
+        state.pressed.insert("ControlLeft".to_string(), 2);
+        state.pressed.insert("KeyA".to_string(), 0);
+        assert_eq!(state.modifiers(), 10);
+
+        state.pressed.remove("ShiftRight");
+        assert_eq!(state.modifiers(), 10);
+        state.pressed.remove("ShiftLeft");
+        assert_eq!(state.modifiers(), 2);
+    }
+
+    #[test]
+    fn splits_combos() {
+        assert_eq!(split_combo("a"), vec!["a"]);
+        assert_eq!(split_combo("Shift+Tab"), vec!["Shift", "Tab"]);
+        assert_eq!(
+            split_combo("Control+Shift+KeyP"),
+            vec!["Control", "Shift", "KeyP"]
+        );
+    }
+
+    #[test]
+    fn splits_combos_with_plus_key() {
+        assert_eq!(split_combo("+"), vec!["+"]);
+        assert_eq!(split_combo("Shift++"), vec!["Shift", "+"]);
+        assert_eq!(
+            split_combo("Control+Shift++"),
+            vec!["Control", "Shift", "+"]
+        );
+    }
+
+    #[test]
+    fn ignores_empty_modifiers() {
+        assert_eq!(split_combo("Control++a"), vec!["Control", "a"]);
+    }
+}
@@ -1,9 +1,9 @@
 //! Code based on [rust-headless-chrome](https://github.com/atroche/rust-headless-chrome/blob/master/src/browser/tab/element/box_model.rs)
 
-use chromiumoxide_cdp::cdp::browser_protocol::dom::Quad;
+use chromiumoxide_cdp::cdp::browser_protocol::dom::{self, Quad};
 use chromiumoxide_cdp::cdp::browser_protocol::page::Viewport;
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -142,7 +142,7 @@ impl ElementQuad {
     }
 
     /// The most bottom (largest) y-coordinate
-    fn most_bottom(&self) -> f64 {
+    pub fn most_bottom(&self) -> f64 {
         self.top_right
             .y
             .max(self.top_left.y)
@@ -214,6 +214,35 @@ impl ElementQuad {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoundingBox {
+    /// The x-coordinate of the top-left corner
+    pub x: f64,
+    /// The y-coordinate of the top-left corner
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl BoundingBox {
+    /// The smallest box that contains all points of the quad
+    pub fn from_quad(quad: &ElementQuad) -> Self {
+        let x = quad.most_left();
+        let y = quad.most_top();
+        Self {
+            x,
+            y,
+            width: quad.most_right() - x,
+            height: quad.most_bottom() - y,
+        }
+    }
+
+    /// The center of the box
+    pub fn center(&self) -> Point {
+        Point::new(self.x + self.width / 2., self.y + self.height / 2.)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BoxModel {
     pub content: ElementQuad,
@@ -225,6 +254,11 @@ pub struct BoxModel {
 }
 
 impl BoxModel {
+    /// The bounding box of the border-box
+    pub fn bounding_box(&self) -> BoundingBox {
+        BoundingBox::from_quad(&self.border)
+    }
+
     /// Create a `Viewport` equal to the content-box, using a scale of 1.0
     pub fn content_viewport(&self) -> Viewport {
         Viewport {
@@ -269,3 +303,16 @@ impl BoxModel {
         }
     }
 }
+
+impl From<dom::BoxModel> for BoxModel {
+    fn from(model: dom::BoxModel) -> Self {
+        Self {
+            content: ElementQuad::from_quad(&model.content),
+            padding: ElementQuad::from_quad(&model.padding),
+            border: ElementQuad::from_quad(&model.border),
+            margin: ElementQuad::from_quad(&model.margin),
+            width: model.width as u32,
+            height: model.height as u32,
+        }
+    }
+}
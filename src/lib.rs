@@ -76,20 +76,43 @@
 
 #![warn(missing_debug_implementations, rust_2018_idioms)]
 
+pub mod actionability;
 pub mod browser;
 pub(crate) mod cmd;
 pub mod conn;
+pub mod console;
+pub mod content;
+pub mod cookies;
+pub mod coverage;
+pub mod debugger;
+pub mod devices;
+pub mod dialog;
 pub mod element;
 pub mod error;
+pub mod file_chooser;
 pub mod handler;
+pub mod io;
+pub mod js;
+pub mod keyboard;
 pub mod keys;
 pub mod layout;
+pub(crate) mod listeners;
+pub mod metrics;
+pub mod mouse;
 pub mod page;
+pub mod pdf;
+pub mod screencast;
+pub mod selector;
+pub mod storage;
+pub mod touchscreen;
+pub mod wait;
 
 pub use crate::browser::{Browser, BrowserConfig};
 pub use crate::conn::Connection;
 pub use crate::element::Element;
+pub use crate::handler::viewport::Viewport;
 pub use crate::handler::Handler;
+pub use crate::js::JsHandle;
 pub use crate::page::Page;
 
 /// reexport all the generated cdp types
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use futures::channel::mpsc::UnboundedSender;
+
+use chromiumoxide_cdp::cdp::events::{CdpEvent, CdpEventMessage};
+
+/// A request to receive all events of the given kinds
+#[derive(Debug)]
+pub struct EventListenerRequest {
+    /// The identifiers of the events to listen for, like
+    /// `"Runtime.consoleAPICalled"`
+    pub methods: Vec<Cow<'static, str>>,
+    /// The sender half of the channel the events are forwarded to
+    pub listener: UnboundedSender<CdpEvent>,
+}
+
+impl EventListenerRequest {
+    pub fn new<I, S>(methods: I, listener: UnboundedSender<CdpEvent>) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<Cow<'static, str>>,
+    {
+        Self {
+            methods: methods.into_iter().map(Into::into).collect(),
+            listener,
+        }
+    }
+}
+
+/// Keeps track of all the subscribers to events of a target
+#[derive(Debug, Default)]
+pub struct EventListeners {
+    /// All the listeners mapped by the identifier of the event they subscribed
+    /// to
+    listeners: HashMap<Cow<'static, str>, Vec<UnboundedSender<CdpEvent>>>,
+}
+
+impl EventListeners {
+    /// Register a new listener for all the methods of the request
+    pub fn add_listener(&mut self, req: EventListenerRequest) {
+        let EventListenerRequest { methods, listener } = req;
+        for method in methods {
+            self.listeners
+                .entry(method)
+                .or_default()
+                .push(listener.clone());
+        }
+    }
+
+    /// Whether there is at least one active listener for the event
+    pub fn has_listener(&mut self, method: &str) -> bool {
+        if let Some(listeners) = self.listeners.get_mut(method) {
+            listeners.retain(|l| !l.is_closed());
+            !listeners.is_empty()
+        } else {
+            false
+        }
+    }
+
+    /// Forwards the event to all its listeners and removes those that are no
+    /// longer active.
+    pub fn start_send(&mut self, event: &CdpEventMessage) {
+        if let Some(listeners) = self.listeners.get_mut(event.method.as_ref()) {
+            listeners.retain(|l| l.unbounded_send(event.params.clone()).is_ok());
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::ops::Sub;
+
+use chromiumoxide_cdp::cdp::browser_protocol::performance::Metric;
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+
+/// A snapshot of the runtime metrics of a page, as reported by
+/// `Performance.getMetrics`.
+///
+/// Durations are in seconds, sizes in bytes.
+///
+/// # Example assert a budget for an interaction
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # async fn demo(page: Page) -> Result<()> {
+///     let before = page.metrics().await?;
+///     page.find_element("#load-more").await?.click().await?;
+///     let delta = page.metrics().await?.delta(&before);
+///     assert!(delta.layout_count <= 2.);
+///     assert!(delta.script_duration < 0.05);
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Metrics {
+    /// The time the snapshot was taken, in seconds since an arbitrary point
+    pub timestamp: f64,
+    /// Number of documents in the page
+    pub documents: f64,
+    /// Number of frames in the page
+    pub frames: f64,
+    /// Number of event listeners in the page
+    pub js_event_listeners: f64,
+    /// Number of DOM nodes in the page
+    pub nodes: f64,
+    /// Total number of full or partial page layouts
+    pub layout_count: f64,
+    /// Total number of page style recalculations
+    pub recalc_style_count: f64,
+    /// Combined duration of all page layouts
+    pub layout_duration: f64,
+    /// Combined duration of all page style recalculations
+    pub recalc_style_duration: f64,
+    /// Combined duration of JavaScript execution
+    pub script_duration: f64,
+    /// Combined duration of all tasks performed by the browser
+    pub task_duration: f64,
+    /// Used JavaScript heap size
+    pub js_heap_used_size: f64,
+    /// Total JavaScript heap size
+    pub js_heap_total_size: f64,
+    /// All other metrics reported by chromium, by name
+    pub other: HashMap<String, f64>,
+}
+
+impl Metrics {
+    /// The value of the metric by its chromium name, like `JSHeapUsedSize`
+    pub fn get(&self, name: &str) -> Option<f64> {
+        let value = match name {
+            "Timestamp" => self.timestamp,
+            "Documents" => self.documents,
+            "Frames" => self.frames,
+            "JSEventListeners" => self.js_event_listeners,
+            "Nodes" => self.nodes,
+            "LayoutCount" => self.layout_count,
+            "RecalcStyleCount" => self.recalc_style_count,
+            "LayoutDuration" => self.layout_duration,
+            "RecalcStyleDuration" => self.recalc_style_duration,
+            "ScriptDuration" => self.script_duration,
+            "TaskDuration" => self.task_duration,
+            "JSHeapUsedSize" => self.js_heap_used_size,
+            "JSHeapTotalSize" => self.js_heap_total_size,
+            name => return self.other.get(name).copied(),
+        };
+        Some(value)
+    }
+
+    fn set(&mut self, name: String, value: f64) {
+        let field = match name.as_str() {
+            "Timestamp" => &mut self.timestamp,
+            "Documents" => &mut self.documents,
+            "Frames" => &mut self.frames,
+            "JSEventListeners" => &mut self.js_event_listeners,
+            "Nodes" => &mut self.nodes,
+            "LayoutCount" => &mut self.layout_count,
+            "RecalcStyleCount" => &mut self.recalc_style_count,
+            "LayoutDuration" => &mut self.layout_duration,
+            "RecalcStyleDuration" => &mut self.recalc_style_duration,
+            "ScriptDuration" => &mut self.script_duration,
+            "TaskDuration" => &mut self.task_duration,
+            "JSHeapUsedSize" => &mut self.js_heap_used_size,
+            "JSHeapTotalSize" => &mut self.js_heap_total_size,
+            _ => {
+                self.other.insert(name, value);
+                return;
+            }
+        };
+        *field = value;
+    }
+
+    /// The difference of all metrics between the `earlier` snapshot and this
+    /// one.
+    ///
+    /// Counters and durations become the amount that was added in between,
+    /// gauges like `nodes` or `js_heap_used_size` how much they changed.
+    /// Metrics that are missing in the `earlier` snapshot are kept as is.
+    pub fn delta(&self, earlier: &Metrics) -> Metrics {
+        Metrics {
+            timestamp: self.timestamp - earlier.timestamp,
+            documents: self.documents - earlier.documents,
+            frames: self.frames - earlier.frames,
+            js_event_listeners: self.js_event_listeners - earlier.js_event_listeners,
+            nodes: self.nodes - earlier.nodes,
+            layout_count: self.layout_count - earlier.layout_count,
+            recalc_style_count: self.recalc_style_count - earlier.recalc_style_count,
+            layout_duration: self.layout_duration - earlier.layout_duration,
+            recalc_style_duration: self.recalc_style_duration - earlier.recalc_style_duration,
+            script_duration: self.script_duration - earlier.script_duration,
+            task_duration: self.task_duration - earlier.task_duration,
+            js_heap_used_size: self.js_heap_used_size - earlier.js_heap_used_size,
+            js_heap_total_size: self.js_heap_total_size - earlier.js_heap_total_size,
+            other: self
+                .other
+                .iter()
+                .map(|(name, value)| {
+                    let delta = value - earlier.other.get(name).copied().unwrap_or_default();
+                    (name.clone(), delta)
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Vec<Metric>> for Metrics {
+    fn from(metrics: Vec<Metric>) -> Self {
+        let mut snapshot = Metrics::default();
+        for metric in metrics {
+            snapshot.set(metric.name, metric.value);
+        }
+        snapshot
+    }
+}
+
+impl Sub for &Metrics {
+    type Output = Metrics;
+
+    fn sub(self, earlier: &Metrics) -> Metrics {
+        self.delta(earlier)
+    }
+}
+
+/// The metrics of a page that are reported whenever `console.timeStamp` is
+/// called with a title
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsEvent {
+    /// The title passed to `console.timeStamp`
+    pub title: String,
+    pub metrics: Metrics,
+}
+
+impl MetricsEvent {
+    pub(crate) fn from_event(event: CdpEvent) -> Option<Self> {
+        if let CdpEvent::PerformanceMetrics(ev) = event {
+            Some(Self {
+                title: ev.title,
+                metrics: ev.metrics.into(),
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(metrics: &[(&str, f64)]) -> Metrics {
+        metrics
+            .iter()
+            .map(|(name, value)| Metric::new(*name, *value))
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn known_and_other_metrics_by_name() {
+        let metrics = snapshot(&[("LayoutCount", 3.), ("FirstMeaningfulPaint", 1.5)]);
+        assert_eq!(metrics.layout_count, 3.);
+        assert_eq!(metrics.get("LayoutCount"), Some(3.));
+        assert_eq!(metrics.get("FirstMeaningfulPaint"), Some(1.5));
+        assert_eq!(metrics.get("Unknown"), None);
+    }
+
+    #[test]
+    fn delta_between_snapshots() {
+        let before = snapshot(&[
+            ("Timestamp", 10.),
+            ("LayoutCount", 3.),
+            ("ScriptDuration", 0.25),
+            ("Nodes", 120.),
+            ("ThreadTime", 1.),
+        ]);
+        let after = snapshot(&[
+            ("Timestamp", 12.5),
+            ("LayoutCount", 5.),
+            ("ScriptDuration", 0.75),
+            ("Nodes", 100.),
+            ("ThreadTime", 3.),
+            ("ProcessTime", 2.),
+        ]);
+        let delta = after.delta(&before);
+        assert_eq!(delta.timestamp, 2.5);
+        assert_eq!(delta.layout_count, 2.);
+        assert_eq!(delta.script_duration, 0.5);
+        // gauges can shrink
+        assert_eq!(delta.nodes, -20.);
+        assert_eq!(delta.get("ThreadTime"), Some(2.));
+        // missing in the earlier snapshot
+        assert_eq!(delta.get("ProcessTime"), Some(2.));
+        assert_eq!(&after - &before, delta);
+    }
+}
@@ -0,0 +1,308 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::{FutureExt, StreamExt};
+use serde::Serialize;
+
+use chromiumoxide_cdp::cdp::browser_protocol::input::MouseButton;
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+use chromiumoxide_types::{Command, Method};
+
+use crate::error::Result;
+use crate::handler::PageInner;
+use crate::layout::Point;
+
+/// Dispatches mouse events to a page.
+///
+/// The current position of the mouse and the buttons that are currently
+/// pressed are tracked per page, so all `Mouse` handles of the same page share
+/// the same state.
+///
+/// # Example drag the mouse while holding the left button
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::layout::Point;
+/// # use chromiumoxide::cdp::browser_protocol::input::MouseButton;
+/// # async fn demo(page: Page) -> Result<()> {
+///     let mouse = page.mouse();
+///     mouse.move_to(Point::new(10., 10.)).await?;
+///     mouse.down(MouseButton::Left).await?;
+///     mouse.move_to_with_steps(Point::new(100., 100.), 10).await?;
+///     mouse.up(MouseButton::Left).await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Mouse {
+    tab: Arc<PageInner>,
+}
+
+impl Mouse {
+    pub(crate) fn new(tab: Arc<PageInner>) -> Self {
+        Self { tab }
+    }
+
+    /// The last position the mouse was moved to
+    pub fn position(&self) -> Point {
+        self.tab.mouse_state().position
+    }
+
+    /// All the buttons that are currently pressed
+    pub fn pressed_buttons(&self) -> Vec<MouseButton> {
+        let buttons = self.tab.mouse_state().buttons;
+        BUTTONS
+            .iter()
+            .filter(|button| buttons & button_mask(button) != 0)
+            .cloned()
+            .collect()
+    }
+
+    /// Moves the mouse to the point with a single `mouseMoved` event
+    pub async fn move_to(&self, point: Point) -> Result<&Self> {
+        self.move_to_with_steps(point, 1).await
+    }
+
+    /// Moves the mouse to the point in `steps` intermediate `mouseMoved`
+    /// events, interpolated linearly from the current position.
+    pub async fn move_to_with_steps(&self, point: Point, steps: usize) -> Result<&Self> {
+        self.tab.mouse_move(point, steps).await?;
+        Ok(self)
+    }
+
+    /// Presses the button at the current position
+    pub async fn down(&self, opts: impl Into<ClickOptions>) -> Result<&Self> {
+        let opts = opts.into();
+        self.tab.mouse_down(opts.button, opts.click_count).await?;
+        Ok(self)
+    }
+
+    /// Releases the button at the current position
+    pub async fn up(&self, opts: impl Into<ClickOptions>) -> Result<&Self> {
+        let opts = opts.into();
+        self.tab.mouse_up(opts.button, opts.click_count).await?;
+        Ok(self)
+    }
+
+    /// Moves the mouse to the point and clicks it.
+    ///
+    /// A `click_count` of 2 is a double click, each click of the sequence
+    /// is dispatched as separate `mousePressed` and `mouseReleased` events.
+    pub async fn click(&self, point: Point, opts: impl Into<ClickOptions>) -> Result<&Self> {
+        self.tab.click_with(point, opts.into()).await?;
+        Ok(self)
+    }
+
+    /// Dispatches a `mouseWheel` event at the current position, scrolling by
+    /// the deltas in CSS pixels.
+    pub async fn wheel(&self, delta_x: f64, delta_y: f64) -> Result<&Self> {
+        self.tab.mouse_wheel(delta_x, delta_y).await?;
+        Ok(self)
+    }
+
+    /// Presses the left button at `from`, moves the mouse to `to` and releases
+    /// the button there.
+    ///
+    /// If the move starts a native HTML drag, the drag is intercepted and its
+    /// data is dropped at `to` via `dragenter`, `dragover` and `drop` events.
+    /// Otherwise only the mouse events are dispatched, which drives pointer
+    /// based drag implementations.
+    ///
+    /// # Example reorder the items of a sortable list
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let first = page.find_element("#list li:first-child").await?;
+    ///     let last = page.find_element("#list li:last-child").await?;
+    ///     let from = first.clickable_point().await?;
+    ///     let to = last.clickable_point().await?;
+    ///     page.mouse().drag_and_drop(from, to).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn drag_and_drop(&self, from: Point, to: Point) -> Result<&Self> {
+        self.tab.execute(SetInterceptDragsParams::new(true)).await?;
+        let res = self.drag_and_drop_intercepted(from, to).await;
+        // release the button even if the drag failed, so it is not stuck
+        let released = if self.tab.mouse_state().buttons & button_mask(&MouseButton::Left) != 0 {
+            self.up(MouseButton::Left).await.map(|_| ())
+        } else {
+            Ok(())
+        };
+        self.tab
+            .execute(SetInterceptDragsParams::new(false))
+            .await?;
+        res?;
+        released?;
+        Ok(self)
+    }
+
+    async fn drag_and_drop_intercepted(&self, from: Point, to: Point) -> Result<()> {
+        let mut intercepted = self.tab.event_listener(vec![DRAG_INTERCEPTED]).await?;
+        self.move_to(from).await?;
+        self.down(MouseButton::Left).await?;
+        self.move_to_with_steps(to, DRAG_STEPS).await?;
+
+        // the drag is reported while handling one of the moves
+        let mut data = None;
+        while let Some(Some(event)) = intercepted.next().now_or_never() {
+            if let CdpEvent::Other(mut params) = event {
+                data = Some(params["data"].take());
+            }
+        }
+        if let Some(data) = data {
+            let modifiers = self.tab.keyboard_state().modifiers();
+            for r#type in ["dragEnter", "dragOver", "drop"] {
+                self.tab
+                    .execute(DispatchDragEventParams {
+                        r#type,
+                        x: to.x,
+                        y: to.y,
+                        data: data.clone(),
+                        modifiers,
+                    })
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configures which button to press and how often
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClickOptions {
+    /// The button to press, defaults to `MouseButton::Left`
+    pub button: MouseButton,
+    /// The number of consecutive clicks, defaults to 1
+    pub click_count: i64,
+    /// How long to wait between pressing and releasing the button
+    pub delay: Option<Duration>,
+}
+
+impl ClickOptions {
+    pub fn button(mut self, button: MouseButton) -> Self {
+        self.button = button;
+        self
+    }
+
+    pub fn click_count(mut self, click_count: i64) -> Self {
+        self.click_count = click_count;
+        self
+    }
+
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+}
+
+impl Default for ClickOptions {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            click_count: 1,
+            delay: None,
+        }
+    }
+}
+
+impl From<MouseButton> for ClickOptions {
+    fn from(button: MouseButton) -> Self {
+        ClickOptions::default().button(button)
+    }
+}
+
+/// The tracked state of the mouse of a page
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MouseState {
+    /// The last position the mouse was moved to
+    pub position: Point,
+    /// Bitmask of the currently pressed buttons
+    pub buttons: i64,
+}
+
+impl MouseState {
+    /// The button reported with `mouseMoved` events, the first pressed one
+    pub fn moving_button(&self) -> MouseButton {
+        BUTTONS
+            .iter()
+            .find(|button| self.buttons & button_mask(button) != 0)
+            .cloned()
+            .unwrap_or(MouseButton::None)
+    }
+}
+
+/// The event that reports the data of an intercepted drag
+const DRAG_INTERCEPTED: &str = "Input.dragIntercepted";
+
+/// `Input.setInterceptDrags`, which is newer than the protocol the types are
+/// generated from
+#[derive(Debug, Clone, Serialize)]
+struct SetInterceptDragsParams {
+    enabled: bool,
+}
+
+impl SetInterceptDragsParams {
+    fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+}
+
+impl Method for SetInterceptDragsParams {
+    fn identifier(&self) -> Cow<'static, str> {
+        "Input.setInterceptDrags".into()
+    }
+}
+
+impl Command for SetInterceptDragsParams {
+    type Response = serde_json::Value;
+}
+
+/// `Input.dispatchDragEvent`, which is newer than the protocol the types are
+/// generated from
+#[derive(Debug, Clone, Serialize)]
+struct DispatchDragEventParams {
+    r#type: &'static str,
+    x: f64,
+    y: f64,
+    /// The `DragData` as reported by `Input.dragIntercepted`
+    data: serde_json::Value,
+    modifiers: i64,
+}
+
+impl Method for DispatchDragEventParams {
+    fn identifier(&self) -> Cow<'static, str> {
+        "Input.dispatchDragEvent".into()
+    }
+}
+
+impl Command for DispatchDragEventParams {
+    type Response = serde_json::Value;
+}
+
+/// Number of `mouseMoved` events dispatched while dragging
+const DRAG_STEPS: usize = 10;
+
+const BUTTONS: [MouseButton; 5] = [
+    MouseButton::Left,
+    MouseButton::Right,
+    MouseButton::Middle,
+    MouseButton::Back,
+    MouseButton::Forward,
+];
+
+/// The bit of the button in the `buttons` bitmask of a mouse event
+pub(crate) fn button_mask(button: &MouseButton) -> i64 {
+    match button {
+        MouseButton::None => 0,
+        MouseButton::Left => 1,
+        MouseButton::Right => 2,
+        MouseButton::Middle => 4,
+        MouseButton::Back => 8,
+        MouseButton::Forward => 16,
+    }
+}
@@ -1,26 +1,60 @@
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
 use futures::channel::oneshot::channel as oneshot_channel;
-use futures::SinkExt;
+use futures::{future, SinkExt, Stream, StreamExt};
 
 use chromiumoxide_cdp::cdp::browser_protocol;
 use chromiumoxide_cdp::cdp::browser_protocol::dom::*;
+use chromiumoxide_cdp::cdp::browser_protocol::emulation::{
+    MediaFeature, SetCpuThrottlingRateParams, SetEmulatedVisionDeficiencyParams,
+    SetEmulatedVisionDeficiencyType, SetGeolocationOverrideParams, SetLocaleOverrideParams,
+    SetTimezoneOverrideParams,
+};
+use chromiumoxide_cdp::cdp::browser_protocol::io::StreamHandle;
+use chromiumoxide_cdp::cdp::browser_protocol::log::EventEntryAdded;
 use chromiumoxide_cdp::cdp::browser_protocol::network::{
-    Cookie, GetCookiesParams, SetUserAgentOverrideParams,
+    Cookie, CookieParam, DeleteCookiesParams, GetCookiesParams, Headers, SetCacheDisabledParams,
+    SetCookiesParams, SetExtraHttpHeadersParams, SetUserAgentOverrideParams,
 };
 use chromiumoxide_cdp::cdp::browser_protocol::page::*;
+use chromiumoxide_cdp::cdp::browser_protocol::performance::{EventMetrics, GetMetricsParams};
+use chromiumoxide_cdp::cdp::browser_protocol::storage;
 use chromiumoxide_cdp::cdp::browser_protocol::target::{ActivateTargetParams, SessionId, TargetId};
 use chromiumoxide_cdp::cdp::js_protocol;
 use chromiumoxide_cdp::cdp::js_protocol::debugger::GetScriptSourceParams;
-use chromiumoxide_cdp::cdp::js_protocol::runtime::{EvaluateParams, RemoteObject, ScriptId};
+use chromiumoxide_cdp::cdp::js_protocol::profiler::{self, Profile, SetSamplingIntervalParams};
+use chromiumoxide_cdp::cdp::js_protocol::runtime::{
+    EvaluateParams, EventConsoleApiCalled, EventExceptionThrown, RemoteObject, ScriptId,
+};
 use chromiumoxide_types::*;
 
+use crate::console::{ConsoleMessage, PageError};
+use crate::content::{Tag, TagContent};
+use crate::coverage::Coverage;
+use crate::debugger::Debugger;
+use crate::devices::Device;
+use crate::dialog::Dialog;
 use crate::element::Element;
 use crate::error::{CdpError, Result};
+use crate::file_chooser::FileChoosers;
+use crate::handler::emulation::{EmulationOverride, MediaType};
+use crate::handler::network::{HttpResponse, NetworkConditions, NetworkOverride};
 use crate::handler::target::TargetMessage;
+use crate::handler::viewport::Viewport;
 use crate::handler::PageInner;
+use crate::io::{IoExecutor, IoStream};
+use crate::js::JsHandle;
+use crate::keyboard::Keyboard;
 use crate::layout::Point;
+use crate::metrics::{Metrics, MetricsEvent};
+use crate::mouse::Mouse;
+use crate::screencast::ScreencastFrame;
+use crate::selector::Selector;
+use crate::storage::{OriginState, StorageState, COLLECT_WEB_STORAGE};
+use crate::touchscreen::Touchscreen;
+use crate::wait::{self, WaitOptions, WaitUntil};
 
 #[derive(Debug)]
 pub struct Page {
@@ -55,6 +89,103 @@ impl Page {
         Ok(self)
     }
 
+    /// Navigate to the given URL and return the response of the main
+    /// resource.
+    ///
+    /// This resolves after the requested URL is fully loaded, with `None` if
+    /// the navigation stayed within the same document, e.g. for a different
+    /// fragment, or loaded no resource from the network like `about:blank`.
+    ///
+    /// # Example assert that the page was served successfully
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let response = page
+    ///         .goto_with_response("https://example.com")
+    ///         .await?
+    ///         .expect("no response");
+    ///     assert_eq!(response.status(), 200);
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn goto_with_response(
+        &self,
+        params: impl Into<NavigateParams>,
+    ) -> Result<Option<HttpResponse>> {
+        let res = self.execute(params.into()).await?;
+        if let Some(err) = res.result.error_text {
+            return Err(CdpError::ChromeMessage(err));
+        }
+        if let Some(loader_id) = res.result.loader_id {
+            Ok(self
+                .response()
+                .await?
+                .filter(|resp| resp.loader_id() == &loader_id))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// The response of the main resource of the document the page currently
+    /// shows, regardless of how it was navigated to.
+    pub async fn response(&self) -> Result<Option<HttpResponse>> {
+        let (tx, rx) = oneshot_channel();
+        self.inner
+            .sender()
+            .clone()
+            .send(TargetMessage::MainResponse(tx))
+            .await?;
+        Ok(rx.await?)
+    }
+
+    /// Reloads the page, bypassing the cache if `ignore_cache` is set.
+    ///
+    /// This resolves with the response of the main resource after the page is
+    /// loaded again.
+    pub async fn reload(&self, ignore_cache: bool) -> Result<Option<HttpResponse>> {
+        self.execute(ReloadParams::builder().ignore_cache(ignore_cache).build())
+            .await?;
+        self.response().await
+    }
+
+    /// Navigates to the previous entry of the history.
+    ///
+    /// This resolves with the response of the main resource after the page is
+    /// loaded, or with `None` if there is no previous entry.
+    pub async fn go_back(&self) -> Result<Option<HttpResponse>> {
+        self.go_to_history_entry(-1).await
+    }
+
+    /// Navigates to the next entry of the history.
+    ///
+    /// This resolves with the response of the main resource after the page is
+    /// loaded, or with `None` if there is no next entry.
+    pub async fn go_forward(&self) -> Result<Option<HttpResponse>> {
+        self.go_to_history_entry(1).await
+    }
+
+    /// Navigates to the history entry `delta` entries away from the current
+    /// one
+    async fn go_to_history_entry(&self, delta: i64) -> Result<Option<HttpResponse>> {
+        let history = self
+            .execute(GetNavigationHistoryParams::default())
+            .await?
+            .result;
+        let idx = history.current_index + delta;
+        if idx < 0 {
+            return Ok(None);
+        }
+        if let Some(entry) = history.entries.get(idx as usize) {
+            self.execute(NavigateToHistoryEntryParams::new(entry.id))
+                .await?;
+            self.response().await
+        } else {
+            Ok(None)
+        }
+    }
+
     /// The identifier of the `Target` this page belongs to
     pub fn target_id(&self) -> &TargetId {
         self.inner.target_id()
@@ -88,11 +219,15 @@ impl Page {
     }
 
     /// Allows overriding user agent with the given string.
+    ///
+    /// The user agent is re-applied if the page is reloaded after a crash.
     pub async fn set_user_agent(
         &self,
         params: impl Into<SetUserAgentOverrideParams>,
     ) -> Result<&Self> {
-        self.execute(params.into()).await?;
+        self.inner
+            .emulate(EmulationOverride::UserAgent(params.into()))
+            .await?;
         Ok(self)
     }
 
@@ -105,23 +240,130 @@ impl Page {
         Ok(resp.result.root)
     }
 
-    /// Returns the first element in the document which matches the given CSS
+    /// Returns the first element in the document which matches the given
     /// selector.
     ///
-    /// Execute a query selector on the document's node.
-    pub async fn find_element(&self, selector: impl Into<String>) -> Result<Element> {
+    /// Strings are treated as CSS selectors, see [`Selector`] for other kinds
+    /// of queries.
+    ///
+    /// # Example find a button by its accessible name
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::selector::Selector;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let css = page.find_element("form button.submit").await?;
+    ///     let xpath = page.find_element(Selector::xpath("//form//button")).await?;
+    ///     let aria = page.find_element(Selector::role_with_name("button", "Submit")).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn find_element(&self, selector: impl Into<Selector>) -> Result<Element> {
         let root = self.get_document().await?.node_id;
         let node_id = self.inner.find_element(selector, root).await?;
         Ok(Element::new(Arc::clone(&self.inner), node_id).await?)
     }
 
     /// Return all `Element`s in the document that match the given selector
-    pub async fn find_elements(&self, selector: impl Into<String>) -> Result<Vec<Element>> {
+    pub async fn find_elements(&self, selector: impl Into<Selector>) -> Result<Vec<Element>> {
         let root = self.get_document().await?.node_id;
         let node_ids = self.inner.find_elements(selector, root).await?;
         Ok(Element::from_nodes(&self.inner, &node_ids).await?)
     }
 
+    /// Waits until an element matching the `selector` is present in the
+    /// document and returns it.
+    ///
+    /// Strings are treated as CSS selectors, see [`Selector`] for other kinds
+    /// of queries.
+    ///
+    /// With `WaitOptions::visible` this waits until the element is also
+    /// visible. With `WaitOptions::hidden` this waits until the element is
+    /// either removed from the document or hidden and resolves with `None`.
+    ///
+    /// Fails with `CdpError::WaitTimeout` if the element did not appear
+    /// within `WaitOptions::timeout`.
+    ///
+    /// # Example wait for an element that is rendered asynchronously
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::wait::WaitOptions;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let element = page
+    ///         .wait_for_selector("#results li", WaitOptions::default().visible(true))
+    ///         .await?
+    ///         .unwrap();
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_selector(
+        &self,
+        selector: impl Into<Selector>,
+        opts: WaitOptions,
+    ) -> Result<Option<Element>> {
+        let selector = selector.into();
+        let function_declaration = match wait::wait_for_selector_fn(&selector, &opts) {
+            Some(function_declaration) => function_declaration,
+            None => {
+                let root = self.get_document().await?.node_id;
+                return Element::poll_for_selector(&self.inner, &selector, root, &opts).await;
+            }
+        };
+        let obj = self
+            .inner
+            .wait_for(
+                function_declaration,
+                None,
+                || opts.describe_selector(&selector),
+                opts.timeout,
+            )
+            .await?;
+        if obj.object_id.is_some() {
+            self.get_document().await?;
+        }
+        Element::from_wait_result(&self.inner, obj).await
+    }
+
+    /// Waits until the javascript `js` evaluates to a truthy value and returns
+    /// that value.
+    ///
+    /// `js` is either an expression or a function that is evaluated
+    /// repeatedly, as configured by `WaitOptions::polling`. If it returns a
+    /// promise, the resolved value is used.
+    ///
+    /// # Example wait until the window has a certain width
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::wait::Polling;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.wait_for_function("window.innerWidth < 100", Polling::Raf).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_function(
+        &self,
+        js: impl AsRef<str>,
+        opts: impl Into<WaitOptions>,
+    ) -> Result<JsHandle> {
+        let js = js.as_ref();
+        let opts = opts.into();
+        let obj = self
+            .inner
+            .wait_for(
+                wait::wait_for_function_fn(js, &opts),
+                None,
+                || format!("function `{}`", js),
+                opts.timeout,
+            )
+            .await?;
+        Ok(JsHandle::new(Arc::clone(&self.inner), obj))
+    }
+
     /// Describes node given its id
     pub async fn describe_node(&self, node_id: NodeId) -> Result<Node> {
         let resp = self
@@ -139,6 +381,240 @@ impl Page {
         todo!()
     }
 
+    /// Emulates the viewport for this page.
+    ///
+    /// If this enables or disables mobile or touch emulation, the page is
+    /// reloaded for the change to take effect.
+    pub async fn set_viewport(&self, viewport: Viewport) -> Result<&Self> {
+        self.inner.set_viewport(viewport).await?;
+        Ok(self)
+    }
+
+    /// Emulates the device by overriding the user agent and viewport of this
+    /// page.
+    ///
+    /// # Example emulate an iPhone
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::devices;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.emulate(&devices::IPHONE_13).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn emulate(&self, device: &Device) -> Result<&Self> {
+        self.set_user_agent(device.user_agent).await?;
+        self.set_viewport(device.viewport.clone()).await
+    }
+
+    /// Overrides the geolocation of this page.
+    ///
+    /// The page also needs the `geolocation` permission to access it.
+    pub async fn set_geolocation(
+        &self,
+        latitude: f64,
+        longitude: f64,
+        accuracy: Option<f64>,
+    ) -> Result<&Self> {
+        let params = SetGeolocationOverrideParams::builder()
+            .latitude(latitude)
+            .longitude(longitude)
+            .accuracy(accuracy.unwrap_or_default())
+            .build();
+        self.inner
+            .emulate(EmulationOverride::Geolocation(params))
+            .await?;
+        Ok(self)
+    }
+
+    /// Emulates position unavailable for this page
+    pub async fn clear_geolocation(&self) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::Geolocation(Default::default()))
+            .await?;
+        Ok(self)
+    }
+
+    /// Overrides the timezone of this page with an ICU timezone id, like
+    /// `"Europe/Berlin"`.
+    ///
+    /// An empty `timezone_id` restores the default timezone.
+    pub async fn set_timezone(&self, timezone_id: impl Into<String>) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::Timezone(SetTimezoneOverrideParams::new(
+                timezone_id,
+            )))
+            .await?;
+        Ok(self)
+    }
+
+    /// Overrides the ICU locale of this page, like `"de_DE"`.
+    ///
+    /// An empty `locale` restores the default locale.
+    pub async fn set_locale(&self, locale: impl Into<String>) -> Result<&Self> {
+        let locale = locale.into();
+        let params = SetLocaleOverrideParams {
+            locale: Some(locale).filter(|locale| !locale.is_empty()),
+        };
+        self.inner
+            .emulate(EmulationOverride::Locale(params))
+            .await?;
+        Ok(self)
+    }
+
+    /// Emulates the media type for CSS media queries, `None` disables the
+    /// emulation.
+    pub async fn emulate_media_type(&self, media: Option<MediaType>) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::MediaType(
+                media.map(|media| media.as_ref().to_string()),
+            ))
+            .await?;
+        Ok(self)
+    }
+
+    /// Emulates the media features for CSS media queries, replacing all
+    /// previously emulated features.
+    ///
+    /// # Example emulate dark mode
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::cdp::browser_protocol::emulation::MediaFeature;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.emulate_media_features(vec![
+    ///         MediaFeature::new("prefers-color-scheme", "dark"),
+    ///         MediaFeature::new("prefers-reduced-motion", "reduce"),
+    ///     ])
+    ///     .await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn emulate_media_features(
+        &self,
+        features: impl IntoIterator<Item = MediaFeature>,
+    ) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::MediaFeatures(
+                features.into_iter().collect(),
+            ))
+            .await?;
+        Ok(self)
+    }
+
+    /// Emulates the vision deficiency, `SetEmulatedVisionDeficiencyType::None`
+    /// disables the emulation.
+    pub async fn emulate_vision_deficiency(
+        &self,
+        deficiency: SetEmulatedVisionDeficiencyType,
+    ) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::VisionDeficiency(
+                SetEmulatedVisionDeficiencyParams::new(deficiency),
+            ))
+            .await?;
+        Ok(self)
+    }
+
+    /// Slows down the CPU by the factor, 1 disables the throttling.
+    pub async fn set_cpu_throttling_rate(&self, rate: f64) -> Result<&Self> {
+        self.inner
+            .emulate(EmulationOverride::CpuThrottling(
+                SetCpuThrottlingRateParams::new(rate),
+            ))
+            .await?;
+        Ok(self)
+    }
+
+    /// Emulates the network being offline, or online again.
+    pub async fn set_offline_mode(&self, offline: bool) -> Result<&Self> {
+        self.inner
+            .override_network(NetworkOverride::Offline(offline))
+            .await?;
+        Ok(self)
+    }
+
+    /// Throttles the network of this page, `None` disables the throttling.
+    ///
+    /// # Example emulate a slow 3G connection
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::handler::network::NetworkConditions;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.emulate_network_conditions(NetworkConditions::SLOW_3G).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn emulate_network_conditions(
+        &self,
+        conditions: impl Into<Option<NetworkConditions>>,
+    ) -> Result<&Self> {
+        self.inner
+            .override_network(NetworkOverride::NetworkConditions(conditions.into()))
+            .await?;
+        Ok(self)
+    }
+
+    /// Toggles ignoring the browser cache for each request
+    pub async fn set_cache_enabled(&self, enabled: bool) -> Result<&Self> {
+        self.inner
+            .override_network(NetworkOverride::CacheDisabled(SetCacheDisabledParams::new(
+                !enabled,
+            )))
+            .await?;
+        Ok(self)
+    }
+
+    /// Sends the headers with every request of this page, replacing all
+    /// previously set extra headers.
+    pub async fn set_extra_http_headers<I, K, V>(&self, headers: I) -> Result<&Self>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let headers: serde_json::Map<String, serde_json::Value> = headers
+            .into_iter()
+            .map(|(name, value)| (name.into(), serde_json::Value::String(value.into())))
+            .collect();
+        let headers: Headers = serde_json::from_value(serde_json::Value::Object(headers))?;
+        self.inner
+            .override_network(NetworkOverride::ExtraHeaders(
+                SetExtraHttpHeadersParams::new(headers),
+            ))
+            .await?;
+        Ok(self)
+    }
+
+    /// Toggles bypassing the content security policy of this page, e.g. to
+    /// inject scripts into pages that forbid inline scripts.
+    pub async fn set_bypass_csp(&self, enabled: bool) -> Result<&Self> {
+        self.inner
+            .override_network(NetworkOverride::BypassCsp(SetBypassCspParams::new(enabled)))
+            .await?;
+        Ok(self)
+    }
+
+    /// The mouse of this page, see [`Mouse`]
+    pub fn mouse(&self) -> Mouse {
+        Mouse::new(Arc::clone(&self.inner))
+    }
+
+    /// The keyboard of this page, see [`Keyboard`]
+    pub fn keyboard(&self) -> Keyboard {
+        Keyboard::new(Arc::clone(&self.inner))
+    }
+
+    /// The touchscreen of this page, see [`Touchscreen`]
+    pub fn touchscreen(&self) -> Touchscreen {
+        Touchscreen::new(Arc::clone(&self.inner))
+    }
+
     /// Moves the mouse to this point (dispatches a mouseMoved event)
     pub async fn move_mouse_to_point(&self, point: Point) -> Result<&Self> {
         self.inner.move_mouse_to_point(point).await?;
@@ -171,21 +647,60 @@ impl Page {
 
     /// Print the current page as pdf.
     ///
-    /// See [`PrintToPdfParams`]
+    /// See [`PdfOptions`](crate::pdf::PdfOptions) and [`PrintToPdfParams`]
     ///
     /// # Note Generating a pdf is currently only supported in Chrome headless.
-    pub async fn pdf(&self, opts: PrintToPdfParams) -> Result<Vec<u8>> {
-        let res = self.execute(opts).await?;
+    pub async fn pdf(&self, opts: impl Into<PrintToPdfParams>) -> Result<Vec<u8>> {
+        let res = self.execute(opts.into()).await?;
         Ok(base64::decode(&res.data)?)
     }
 
+    /// Print the current page as pdf and read it in chunks from the returned
+    /// reader, instead of transferring it as a whole.
+    ///
+    /// This keeps the memory footprint low for very large documents.
+    ///
+    /// # Example write a large pdf to a file
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::pdf::PdfOptions;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let pdf = page.pdf_stream(PdfOptions::default()).await?;
+    ///     let mut file = async_std::fs::File::create("report.pdf").await?;
+    ///     futures::io::copy(pdf, &mut file).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Note Generating a pdf is currently only supported in Chrome headless.
+    pub async fn pdf_stream(&self, opts: impl Into<PrintToPdfParams>) -> Result<IoStream> {
+        let mut params = opts.into();
+        params.transfer_mode = Some(PrintToPdfTransferMode::ReturnAsStream);
+        let handle = self
+            .execute(params)
+            .await?
+            .result
+            .stream
+            .ok_or_else(|| CdpError::msg("No stream handle returned for the pdf"))?;
+        Ok(self.read_stream(handle))
+    }
+
+    /// Reads the stream of this page, like the body of a response taken via
+    /// `Fetch.takeResponseBodyAsStream`, see [`IoStream`].
+    pub fn read_stream(&self, handle: StreamHandle) -> IoStream {
+        let executor: Arc<dyn IoExecutor> = self.inner.clone();
+        IoStream::new(executor, handle)
+    }
+
     /// Save the current page as pdf as file to the `output` path and return the
     /// pdf contents.
     ///
     /// # Note Generating a pdf is currently only supported in Chrome headless.
     pub async fn save_pdf(
         &self,
-        opts: PrintToPdfParams,
+        opts: impl Into<PrintToPdfParams>,
         output: impl AsRef<Path>,
     ) -> Result<Vec<u8>> {
         let pdf = self.pdf(opts).await?;
@@ -193,6 +708,89 @@ impl Page {
         Ok(pdf)
     }
 
+    /// Starts a screencast of the page and returns the stream of its frames.
+    ///
+    /// Each frame is acknowledged as soon as it is received, so chromium
+    /// keeps sending new ones until the screencast is stopped with
+    /// [`Page::stop_screencast`]. See
+    /// [`ScreencastOptions`](crate::screencast::ScreencastOptions) and
+    /// [`ImageSequence`](crate::screencast::ImageSequence) to save the frames.
+    pub async fn start_screencast(
+        &self,
+        opts: impl Into<StartScreencastParams>,
+    ) -> Result<impl Stream<Item = ScreencastFrame>> {
+        let params = opts.into();
+        let format = params.format.clone().unwrap_or(StartScreencastFormat::Png);
+        let inner = Arc::clone(&self.inner);
+        let events = self
+            .inner
+            .event_listener(vec![EventScreencastFrame::IDENTIFIER])
+            .await?;
+        self.execute(params).await?;
+        Ok(events
+            .filter_map(move |ev| future::ready(ScreencastFrame::from_event(&inner, &format, ev))))
+    }
+
+    /// Stops the screencast of the page
+    pub async fn stop_screencast(&self) -> Result<&Self> {
+        self.execute(StopScreencastParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Starts the sampling CPU profiler of the page.
+    ///
+    /// The `sampling_interval` defaults to chromium's default of 1ms if not
+    /// set.
+    ///
+    /// # Example profile an interaction and open the profile in DevTools
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.start_cpu_profile(Duration::from_micros(100)).await?;
+    ///     page.find_element("#expensive").await?.click().await?;
+    ///     page.save_cpu_profile("interaction.cpuprofile").await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn start_cpu_profile(
+        &self,
+        sampling_interval: impl Into<Option<Duration>>,
+    ) -> Result<&Self> {
+        self.execute(profiler::EnableParams::default()).await?;
+        if let Some(interval) = sampling_interval.into() {
+            self.execute(SetSamplingIntervalParams::new(
+                interval.as_micros().max(1) as i64
+            ))
+            .await?;
+        }
+        self.execute(profiler::StartParams::default()).await?;
+        Ok(self)
+    }
+
+    /// Stops the CPU profiler and returns the recorded profile.
+    ///
+    /// The `Profiler` domain stays enabled, as JavaScript coverage may still
+    /// be collected with it.
+    pub async fn stop_cpu_profile(&self) -> Result<Profile> {
+        Ok(self
+            .execute(profiler::StopParams::default())
+            .await?
+            .result
+            .profile)
+    }
+
+    /// Stops the CPU profiler and writes the recorded profile to the `output`
+    /// path as `.cpuprofile` file, that can be loaded in the performance
+    /// panel of the DevTools.
+    pub async fn save_cpu_profile(&self, output: impl AsRef<Path>) -> Result<Profile> {
+        let profile = self.stop_cpu_profile().await?;
+        async_std::fs::write(output.as_ref(), serde_json::to_vec(&profile)?).await?;
+        Ok(profile)
+    }
+
     /// Enables log domain. Enabled by default.
     ///
     /// Sends the entries collected so far to the client by means of the
@@ -260,6 +858,264 @@ impl Page {
             .cookies)
     }
 
+    /// Sets the cookie for this page, see `Page::set_cookies`
+    pub async fn set_cookie(&self, cookie: impl Into<CookieParam>) -> Result<&Self> {
+        self.set_cookies(vec![cookie.into()]).await
+    }
+
+    /// Sets the cookies.
+    ///
+    /// Cookies without an `url` or `domain` are set for the current URL of
+    /// this page.
+    pub async fn set_cookies(&self, mut cookies: Vec<CookieParam>) -> Result<&Self> {
+        if cookies
+            .iter()
+            .any(|cookie| cookie.url.is_none() && cookie.domain.is_none())
+        {
+            let url = self.cookie_url().await?;
+            for cookie in cookies.iter_mut() {
+                if cookie.url.is_none() && cookie.domain.is_none() {
+                    cookie.url = Some(url.clone().ok_or_else(|| {
+                        CdpError::msg("Cookies need an url or domain on pages without an http url")
+                    })?);
+                }
+            }
+        }
+        // cookies with the same name, domain and path are replaced
+        self.execute(SetCookiesParams::new(cookies)).await?;
+        Ok(self)
+    }
+
+    /// Deletes the cookie, see `Page::delete_cookies`
+    pub async fn delete_cookie(&self, cookie: impl Into<DeleteCookiesParams>) -> Result<&Self> {
+        self.delete_cookies(vec![cookie.into()]).await
+    }
+
+    /// Deletes the cookies.
+    ///
+    /// Cookies without an `url` or `domain` are deleted for the current URL of
+    /// this page.
+    pub async fn delete_cookies(&self, cookies: Vec<DeleteCookiesParams>) -> Result<&Self> {
+        let mut url = None;
+        for mut cookie in cookies {
+            if cookie.url.is_none() && cookie.domain.is_none() {
+                if url.is_none() {
+                    url = Some(self.cookie_url().await?);
+                }
+                cookie.url = Some(url.clone().flatten().ok_or_else(|| {
+                    CdpError::msg("Cookies need an url or domain on pages without an http url")
+                })?);
+            }
+            self.execute(cookie).await?;
+        }
+        Ok(self)
+    }
+
+    /// The url of this page if cookies can be set for it
+    async fn cookie_url(&self) -> Result<Option<String>> {
+        Ok(self.url().await?.filter(|url| url.starts_with("http")))
+    }
+
+    /// Takes a snapshot of all cookies of the browser, like
+    /// `Browser::get_all_cookies`, and the `localStorage` and
+    /// `sessionStorage` of the origin of the main frame.
+    ///
+    /// The web storage of other origins, including those of cross origin
+    /// iframes, is not collected.
+    pub async fn storage_state(&self) -> Result<StorageState> {
+        let cookies = self
+            .execute(storage::GetCookiesParams::default())
+            .await?
+            .result
+            .cookies;
+        let origin = self
+            .evaluate(
+                EvaluateParams::builder()
+                    .expression(COLLECT_WEB_STORAGE)
+                    .return_by_value(true)
+                    .build()
+                    .map_err(CdpError::msg)?,
+            )
+            .await?
+            .value
+            .map(serde_json::from_value::<Option<OriginState>>)
+            .transpose()?
+            .flatten();
+        Ok(StorageState {
+            cookies,
+            origins: origin.into_iter().collect(),
+        })
+    }
+
+    /// Restores the snapshot: sets its cookies and restores the web storage of
+    /// its origins on every document of them that is loaded from now on.
+    ///
+    /// Restore the state before navigating to one of its origins, or reload
+    /// the page afterwards. Storage items that already exist are not
+    /// overwritten.
+    pub async fn restore_storage_state(&self, state: &StorageState) -> Result<&Self> {
+        if !state.cookies.is_empty() {
+            self.set_cookies(state.cookie_params()).await?;
+        }
+        if !state.origins.is_empty() {
+            self.execute(AddScriptToEvaluateOnNewDocumentParams::new(
+                state.init_script()?,
+            ))
+            .await?;
+        }
+        Ok(self)
+    }
+
+    /// Returns a `Stream` over all messages that are logged to the console of
+    /// this page from now on.
+    ///
+    /// This includes calls to the javascript `console` API as well as entries
+    /// reported by the browser itself, like network errors.
+    ///
+    /// # Example fail on any logged error
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::cdp::browser_protocol::log::LogEntryLevel;
+    /// # use futures::StreamExt;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let mut messages = page.console_messages().await?;
+    ///     while let Some(msg) = messages.next().await {
+    ///         assert_ne!(msg.level, LogEntryLevel::Error, "{}", msg.text);
+    ///     }
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn console_messages(&self) -> Result<impl Stream<Item = ConsoleMessage>> {
+        let inner = Arc::clone(&self.inner);
+        let events = self
+            .inner
+            .event_listener(vec![
+                EventConsoleApiCalled::IDENTIFIER,
+                EventEntryAdded::IDENTIFIER,
+            ])
+            .await?;
+        Ok(events.filter_map(move |ev| future::ready(ConsoleMessage::from_event(&inner, ev))))
+    }
+
+    /// Returns a handle to the JavaScript debugger of this page, see
+    /// [`Debugger`](crate::debugger::Debugger).
+    pub fn debugger(&self) -> Debugger {
+        Debugger::new(Arc::clone(&self.inner))
+    }
+
+    /// Returns a handle to collect the JavaScript and CSS coverage of this
+    /// page, see [`Coverage`](crate::coverage::Coverage).
+    pub fn coverage(&self) -> Coverage {
+        Coverage::new(Arc::clone(&self.inner))
+    }
+
+    /// Returns a snapshot of the runtime metrics of this page, see
+    /// [`Metrics`](crate::metrics::Metrics).
+    pub async fn metrics(&self) -> Result<Metrics> {
+        Ok(self
+            .execute(GetMetricsParams::default())
+            .await?
+            .result
+            .metrics
+            .into())
+    }
+
+    /// Returns a `Stream` over the metrics that are reported whenever the page
+    /// calls `console.timeStamp` with a title from now on.
+    ///
+    /// # Example collect the metrics at marks set by the page
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use futures::StreamExt;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let mut marks = page.metrics_events().await?;
+    ///     page.evaluate("console.timeStamp('rendered')").await?;
+    ///     let mark = marks.next().await.unwrap();
+    ///     assert_eq!(mark.title, "rendered");
+    ///     println!("{} nodes", mark.metrics.nodes);
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn metrics_events(&self) -> Result<impl Stream<Item = MetricsEvent>> {
+        let events = self
+            .inner
+            .event_listener(vec![EventMetrics::IDENTIFIER])
+            .await?;
+        Ok(events.filter_map(|ev| future::ready(MetricsEvent::from_event(ev))))
+    }
+
+    /// Returns a `Stream` over all uncaught exceptions that are thrown in this
+    /// page from now on.
+    pub async fn page_errors(&self) -> Result<impl Stream<Item = PageError>> {
+        let events = self
+            .inner
+            .event_listener(vec![EventExceptionThrown::IDENTIFIER])
+            .await?;
+        Ok(events.filter_map(|ev| future::ready(PageError::from_event(ev))))
+    }
+
+    /// Returns a `Stream` over all javascript dialogs (`alert`, `confirm`,
+    /// `prompt`, `onbeforeunload`) opened by this page from now on.
+    ///
+    /// The page is blocked until each `Dialog` is either accepted or
+    /// dismissed. While this stream is alive, the `DialogPolicy` configured
+    /// for the browser is not applied.
+    ///
+    /// # Example accept all dialogs
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use futures::StreamExt;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let mut dialogs = page.dialogs().await?;
+    ///     while let Some(dialog) = dialogs.next().await {
+    ///         println!("{}", dialog.message());
+    ///         dialog.accept(None).await?;
+    ///     }
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn dialogs(&self) -> Result<impl Stream<Item = Dialog>> {
+        let inner = Arc::clone(&self.inner);
+        let events = self
+            .inner
+            .event_listener(vec![EventJavascriptDialogOpening::IDENTIFIER])
+            .await?;
+        Ok(events.filter_map(move |ev| future::ready(Dialog::from_event(&inner, ev))))
+    }
+
+    /// Returns a `Stream` over all file choosers opened by this page from now
+    /// on.
+    ///
+    /// This enables the interception of file choosers for this page, so
+    /// instead of showing the native dialog, the files of each `FileChooser`
+    /// must be set via `FileChooser::set_files`. Once the returned stream is
+    /// dropped, the native dialog is shown again.
+    ///
+    /// # Example upload a file via the file chooser
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use futures::StreamExt;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     let mut choosers = page.file_choosers().await?;
+    ///     page.find_element("input[type=file]").await?.click().await?;
+    ///     if let Some(chooser) = choosers.next().await {
+    ///         chooser.set_files(vec!["./report.pdf"]).await?;
+    ///     }
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn file_choosers(&self) -> Result<FileChoosers> {
+        FileChoosers::new(Arc::clone(&self.inner)).await
+    }
+
     /// Returns the title of the document.
     pub async fn get_title(&self) -> Result<Option<String>> {
         let remote_object = self.evaluate("document.title").await?;
@@ -301,6 +1157,87 @@ impl Page {
         Ok(serde_json::from_value(value)?)
     }
 
+    /// Replaces the document of the main frame with the `html` and resolves
+    /// once the `wait_until` lifecycle event was fired for it.
+    ///
+    /// # Example render generated HTML as PDF without serving it
+    ///
+    /// ```no_run
+    /// # use chromiumoxide::page::Page;
+    /// # use chromiumoxide::error::Result;
+    /// # use chromiumoxide::wait::WaitUntil;
+    /// # use chromiumoxide::cdp::browser_protocol::page::PrintToPdfParams;
+    /// # async fn demo(page: Page) -> Result<()> {
+    ///     page.set_content("<h1>Report</h1>", WaitUntil::NetworkIdle)
+    ///         .await?;
+    ///     let pdf = page.pdf(PrintToPdfParams::default()).await?;
+    ///     # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_content(
+        &self,
+        html: impl Into<String>,
+        wait_until: WaitUntil,
+    ) -> Result<&Self> {
+        let frame_id = self.mainframe().await?.ok_or(CdpError::NotFound)?;
+        // the new document fires no `init` event that would reset the events
+        // of the current one
+        let mut sender = self.inner.sender().clone();
+        sender.send(TargetMessage::ClearLifecycleEvents).await?;
+        self.execute(SetDocumentContentParams::new(frame_id, html))
+            .await?;
+        let (tx, rx) = oneshot_channel();
+        sender
+            .send(TargetMessage::WaitForLifecycleEvent(
+                wait_until.lifecycle_event(),
+                tx,
+            ))
+            .await?;
+        rx.await?;
+        Ok(self)
+    }
+
+    /// Adds a `<script>` tag with the content to the page and returns it.
+    ///
+    /// Scripts loaded from an url have finished loading when this resolves.
+    pub async fn add_script_tag(&self, content: impl Into<TagContent>) -> Result<Element> {
+        self.add_tag(content.into(), Tag::Script).await
+    }
+
+    /// Adds a `<style>` tag with the content, or a `<link rel="stylesheet">`
+    /// for an url, to the page and returns it.
+    ///
+    /// Stylesheets loaded from an url have finished loading when this
+    /// resolves.
+    pub async fn add_style_tag(&self, content: impl Into<TagContent>) -> Result<Element> {
+        self.add_tag(content.into(), Tag::Style).await
+    }
+
+    async fn add_tag(&self, content: TagContent, tag: Tag) -> Result<Element> {
+        let resp = self
+            .execute(
+                EvaluateParams::builder()
+                    .expression(content.tag_expression(tag)?)
+                    .await_promise(true)
+                    .build()
+                    .map_err(CdpError::msg)?,
+            )
+            .await?
+            .result;
+        if let Some(details) = resp.exception_details {
+            return Err(CdpError::msg(
+                details
+                    .exception
+                    .and_then(|err| err.description)
+                    .unwrap_or(details.text),
+            ));
+        }
+        let object_id = resp.result.object_id.ok_or(CdpError::NotFound)?;
+        // the document needs to be requested before nodes can be resolved
+        self.get_document().await?;
+        Element::from_remote_object(Arc::clone(&self.inner), object_id).await
+    }
+
     /// Returns source for the script with given id.
     ///
     /// Debugger must be enabled.
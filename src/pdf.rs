@@ -0,0 +1,330 @@
+use chromiumoxide_cdp::cdp::browser_protocol::page::PrintToPdfParams;
+
+/// CSS pixels per inch
+const PX_PER_INCH: f64 = 96.;
+const CM_PER_INCH: f64 = 2.54;
+
+/// A length on the printed page
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Length {
+    /// CSS pixels, 96 per inch
+    Px(f64),
+    In(f64),
+    Cm(f64),
+    Mm(f64),
+}
+
+impl Length {
+    pub fn px(px: f64) -> Self {
+        Length::Px(px)
+    }
+
+    pub fn inches(inches: f64) -> Self {
+        Length::In(inches)
+    }
+
+    pub fn cm(cm: f64) -> Self {
+        Length::Cm(cm)
+    }
+
+    pub fn mm(mm: f64) -> Self {
+        Length::Mm(mm)
+    }
+
+    /// The length in inches, the unit expected by `Page.printToPDF`
+    pub fn to_inches(self) -> f64 {
+        match self {
+            Length::Px(px) => px / PX_PER_INCH,
+            Length::In(inches) => inches,
+            Length::Cm(cm) => cm / CM_PER_INCH,
+            Length::Mm(mm) => mm / 10. / CM_PER_INCH,
+        }
+    }
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Length::Px(0.)
+    }
+}
+
+/// Standard paper sizes
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PaperFormat {
+    Letter,
+    Legal,
+    Tabloid,
+    Ledger,
+    A0,
+    A1,
+    A2,
+    A3,
+    A4,
+    A5,
+    A6,
+}
+
+impl PaperFormat {
+    /// Width and height of the paper in portrait orientation in inches
+    pub fn size(self) -> (f64, f64) {
+        match self {
+            PaperFormat::Letter => (8.5, 11.),
+            PaperFormat::Legal => (8.5, 14.),
+            PaperFormat::Tabloid => (11., 17.),
+            PaperFormat::Ledger => (17., 11.),
+            PaperFormat::A0 => (33.1, 46.8),
+            PaperFormat::A1 => (23.4, 33.1),
+            PaperFormat::A2 => (16.54, 23.4),
+            PaperFormat::A3 => (11.7, 16.54),
+            PaperFormat::A4 => (8.27, 11.7),
+            PaperFormat::A5 => (5.83, 8.27),
+            PaperFormat::A6 => (4.13, 5.83),
+        }
+    }
+}
+
+/// The margins of the printed pages
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct PdfMargin {
+    pub top: Length,
+    pub right: Length,
+    pub bottom: Length,
+    pub left: Length,
+}
+
+impl PdfMargin {
+    /// The same margin on all sides
+    pub fn uniform(margin: Length) -> Self {
+        Self {
+            top: margin,
+            right: margin,
+            bottom: margin,
+            left: margin,
+        }
+    }
+
+    /// `vertical` margin at top and bottom, `horizontal` left and right
+    pub fn symmetric(vertical: Length, horizontal: Length) -> Self {
+        Self {
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+            left: horizontal,
+        }
+    }
+}
+
+/// Configures how a page is printed as PDF.
+///
+/// Unset options use the defaults of chromium: US Letter paper, portrait
+/// orientation and margins of about 1cm.
+///
+/// # Example print a report on A4 paper with page numbers
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::pdf::{Length, PaperFormat, PdfMargin, PdfOptions};
+/// # async fn demo(page: Page) -> Result<()> {
+///     let opts = PdfOptions::default()
+///         .format(PaperFormat::A4)
+///         .margin(PdfMargin::uniform(Length::mm(15.)))
+///         .footer_template(
+///             r#"<div style="font-size: 8px; margin: auto">
+///                 <span class="pageNumber"></span> / <span class="totalPages"></span>
+///             </div>"#,
+///         )
+///         .print_background(true);
+///     page.save_pdf(opts, "report.pdf").await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct PdfOptions {
+    pub landscape: Option<bool>,
+    pub print_background: Option<bool>,
+    /// Scale of the rendering, between 0.1 and 2
+    pub scale: Option<f64>,
+    /// Width of the paper, takes precedence over `format`
+    pub width: Option<Length>,
+    /// Height of the paper, takes precedence over `format`
+    pub height: Option<Length>,
+    pub format: Option<PaperFormat>,
+    pub margin: Option<PdfMargin>,
+    /// The pages to print, like `1-5, 8, 11-13`
+    pub page_ranges: Option<String>,
+    /// HTML template for the header of each page.
+    ///
+    /// Elements with the classes `date`, `title`, `url`, `pageNumber` and
+    /// `totalPages` are filled with the respective values.
+    pub header_template: Option<String>,
+    /// HTML template for the footer of each page, like `header_template`
+    pub footer_template: Option<String>,
+    /// Whether a size declared with the CSS `@page` rule takes precedence
+    /// over the paper size
+    pub prefer_css_page_size: Option<bool>,
+}
+
+impl PdfOptions {
+    pub fn landscape(mut self, landscape: bool) -> Self {
+        self.landscape = Some(landscape);
+        self
+    }
+
+    pub fn print_background(mut self, print_background: bool) -> Self {
+        self.print_background = Some(print_background);
+        self
+    }
+
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    pub fn format(mut self, format: PaperFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn margin(mut self, margin: PdfMargin) -> Self {
+        self.margin = Some(margin);
+        self
+    }
+
+    pub fn page_ranges(mut self, page_ranges: impl Into<String>) -> Self {
+        self.page_ranges = Some(page_ranges.into());
+        self
+    }
+
+    pub fn header_template(mut self, template: impl Into<String>) -> Self {
+        self.header_template = Some(template.into());
+        self
+    }
+
+    pub fn footer_template(mut self, template: impl Into<String>) -> Self {
+        self.footer_template = Some(template.into());
+        self
+    }
+
+    pub fn prefer_css_page_size(mut self, prefer_css_page_size: bool) -> Self {
+        self.prefer_css_page_size = Some(prefer_css_page_size);
+        self
+    }
+}
+
+impl From<PdfOptions> for PrintToPdfParams {
+    fn from(opts: PdfOptions) -> Self {
+        let format = opts.format.map(PaperFormat::size);
+        let display_header_footer =
+            opts.header_template.is_some() || opts.footer_template.is_some();
+        let margin = opts.margin;
+        PrintToPdfParams {
+            landscape: opts.landscape,
+            display_header_footer: Some(display_header_footer),
+            print_background: opts.print_background,
+            scale: opts.scale,
+            paper_width: opts
+                .width
+                .map(Length::to_inches)
+                .or(format.map(|(width, _)| width)),
+            paper_height: opts
+                .height
+                .map(Length::to_inches)
+                .or(format.map(|(_, height)| height)),
+            margin_top: margin.map(|m| m.top.to_inches()),
+            margin_bottom: margin.map(|m| m.bottom.to_inches()),
+            margin_left: margin.map(|m| m.left.to_inches()),
+            margin_right: margin.map(|m| m.right.to_inches()),
+            page_ranges: opts.page_ranges,
+            ignore_invalid_page_ranges: None,
+            // chromium prints the default templates if only one is set
+            header_template: opts
+                .header_template
+                .or_else(|| display_header_footer.then(|| "<span></span>".to_string())),
+            footer_template: opts
+                .footer_template
+                .or_else(|| display_header_footer.then(|| "<span></span>".to_string())),
+            prefer_css_page_size: opts.prefer_css_page_size,
+            transfer_mode: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn length_to_inches() {
+        assert_close(Length::px(96.).to_inches(), 1.);
+        assert_close(Length::px(48.).to_inches(), 0.5);
+        assert_close(Length::inches(2.5).to_inches(), 2.5);
+        assert_close(Length::cm(2.54).to_inches(), 1.);
+        assert_close(Length::mm(25.4).to_inches(), 1.);
+        assert_close(Length::default().to_inches(), 0.);
+    }
+
+    #[test]
+    fn default_options_use_chromium_defaults() {
+        let params: PrintToPdfParams = PdfOptions::default().into();
+        assert_eq!(params.paper_width, None);
+        assert_eq!(params.paper_height, None);
+        assert_eq!(params.margin_top, None);
+        assert_eq!(params.display_header_footer, Some(false));
+        assert_eq!(params.header_template, None);
+        assert_eq!(params.footer_template, None);
+    }
+
+    #[test]
+    fn paper_size_from_format_and_explicit_size() {
+        let params: PrintToPdfParams = PdfOptions::default().format(PaperFormat::A4).into();
+        assert_eq!(params.paper_width, Some(8.27));
+        assert_eq!(params.paper_height, Some(11.7));
+
+        // explicit sizes take precedence over the format
+        let params: PrintToPdfParams = PdfOptions::default()
+            .format(PaperFormat::A4)
+            .width(Length::px(480.))
+            .into();
+        assert_eq!(params.paper_width, Some(5.));
+        assert_eq!(params.paper_height, Some(11.7));
+    }
+
+    #[test]
+    fn margins_are_converted_to_inches() {
+        let params: PrintToPdfParams = PdfOptions::default()
+            .margin(PdfMargin::symmetric(Length::inches(1.), Length::px(48.)))
+            .into();
+        assert_eq!(params.margin_top, Some(1.));
+        assert_eq!(params.margin_bottom, Some(1.));
+        assert_eq!(params.margin_left, Some(0.5));
+        assert_eq!(params.margin_right, Some(0.5));
+    }
+
+    #[test]
+    fn single_template_blanks_the_other() {
+        let params: PrintToPdfParams = PdfOptions::default()
+            .footer_template("<span class=\"pageNumber\"></span>")
+            .into();
+        assert_eq!(params.display_header_footer, Some(true));
+        assert_eq!(params.header_template.as_deref(), Some("<span></span>"));
+        assert_eq!(
+            params.footer_template.as_deref(),
+            Some("<span class=\"pageNumber\"></span>")
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures::{Stream, StreamExt};
+
+use chromiumoxide_cdp::cdp::browser_protocol::page::{
+    ScreencastFrameAckParams, ScreencastFrameMetadata, StartScreencastFormat, StartScreencastParams,
+};
+use chromiumoxide_cdp::cdp::events::CdpEvent;
+
+use crate::error::Result;
+use crate::handler::PageInner;
+
+/// Configures how the frames of a screencast are captured.
+///
+/// Unset options use the defaults of chromium: png frames of the size of the
+/// viewport for every frame that is rendered.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ScreencastOptions {
+    pub format: Option<StartScreencastFormat>,
+    /// Compression quality from 0 to 100, only for jpeg
+    pub quality: Option<i64>,
+    pub max_width: Option<i64>,
+    pub max_height: Option<i64>,
+    /// Only send every n-th rendered frame
+    pub every_nth_frame: Option<i64>,
+}
+
+impl ScreencastOptions {
+    pub fn format(mut self, format: StartScreencastFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn quality(mut self, quality: i64) -> Self {
+        self.quality = Some(quality);
+        self
+    }
+
+    pub fn max_width(mut self, max_width: i64) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    pub fn max_height(mut self, max_height: i64) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+
+    pub fn every_nth_frame(mut self, every_nth_frame: i64) -> Self {
+        self.every_nth_frame = Some(every_nth_frame);
+        self
+    }
+}
+
+impl From<ScreencastOptions> for StartScreencastParams {
+    fn from(opts: ScreencastOptions) -> Self {
+        StartScreencastParams {
+            format: opts.format,
+            quality: opts.quality,
+            max_width: opts.max_width,
+            max_height: opts.max_height,
+            every_nth_frame: opts.every_nth_frame,
+        }
+    }
+}
+
+/// A decoded frame of a screencast
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScreencastFrame {
+    /// The encoded image
+    pub data: Vec<u8>,
+    pub format: StartScreencastFormat,
+    /// The size and scroll position of the page, and when the frame was
+    /// captured
+    pub metadata: ScreencastFrameMetadata,
+}
+
+impl ScreencastFrame {
+    /// Decodes the frame and acknowledges it, so chromium sends the next one.
+    pub(crate) fn from_event(
+        tab: &Arc<PageInner>,
+        format: &StartScreencastFormat,
+        event: CdpEvent,
+    ) -> Option<Self> {
+        if let CdpEvent::PageScreencastFrame(ev) = event {
+            tab.send_command(ScreencastFrameAckParams::new(ev.session_id));
+            let data: &str = ev.data.as_ref();
+            // chromium only sends valid base64, skip the frame otherwise
+            let data = base64::decode(data).ok()?;
+            Some(Self {
+                data,
+                format: format.clone(),
+                metadata: ev.metadata,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The file extension of the image
+    pub fn extension(&self) -> &'static str {
+        match self.format {
+            StartScreencastFormat::Jpeg => "jpeg",
+            StartScreencastFormat::Png => "png",
+        }
+    }
+
+    /// Save the image to the `output` path
+    pub async fn save(&self, output: impl AsRef<Path>) -> Result<()> {
+        async_std::fs::write(output.as_ref(), &self.data).await?;
+        Ok(())
+    }
+}
+
+/// Writes screencast frames as numbered images, `frame-000000.png`,
+/// `frame-000001.png` and so on, into a directory.
+///
+/// # Example record a test run
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::screencast::{ImageSequence, ScreencastOptions};
+/// # use chromiumoxide::cdp::browser_protocol::page::StartScreencastFormat;
+/// # use futures::StreamExt;
+/// # async fn run_test(page: &Page) -> Result<()> { Ok(()) }
+/// # async fn demo(page: Page) -> Result<()> {
+///     let opts = ScreencastOptions::default()
+///         .format(StartScreencastFormat::Jpeg)
+///         .quality(80)
+///         .max_width(1280)
+///         .max_height(720);
+///     let frames = page.start_screencast(opts).await?;
+///     let mut sequence = ImageSequence::create("recordings/login").await?;
+///
+///     let (done, test_done) = futures::channel::oneshot::channel::<()>();
+///     let test = async {
+///         let result = run_test(&page).await;
+///         let _ = done.send(());
+///         result
+///     };
+///     let (result, recorded) =
+///         futures::join!(test, sequence.write_all(frames.take_until(test_done)));
+///     recorded?;
+///     page.stop_screencast().await?;
+///     if result.is_ok() {
+///         async_std::fs::remove_dir_all(sequence.dir()).await?;
+///     }
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageSequence {
+    dir: PathBuf,
+    /// The number of frames written so far
+    len: usize,
+}
+
+impl ImageSequence {
+    /// Creates the directory, and all of its parents, if missing
+    pub async fn create(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        async_std::fs::create_dir_all(&dir).await?;
+        Ok(Self { dir, len: 0 })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The number of frames written so far
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes the frame as the next image and returns its path
+    pub async fn write(&mut self, frame: &ScreencastFrame) -> Result<PathBuf> {
+        let path = self
+            .dir
+            .join(format!("frame-{:06}.{}", self.len, frame.extension()));
+        frame.save(&path).await?;
+        self.len += 1;
+        Ok(path)
+    }
+
+    /// Writes all frames until the stream ends and returns how many were
+    /// written
+    pub async fn write_all(
+        &mut self,
+        frames: impl Stream<Item = ScreencastFrame>,
+    ) -> Result<usize> {
+        futures::pin_mut!(frames);
+        let mut written = 0;
+        while let Some(frame) = frames.next().await {
+            self.write(&frame).await?;
+            written += 1;
+        }
+        Ok(written)
+    }
+}
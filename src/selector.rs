@@ -0,0 +1,178 @@
+use std::fmt;
+
+/// Describes how to find elements in a document.
+///
+/// Plain strings are converted into `Selector::Css`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Selector {
+    /// A CSS selector, as understood by `querySelector`
+    Css(String),
+    /// A CSS selector that also matches elements within open shadow roots
+    Pierce(String),
+    /// An XPath expression, evaluated with `document.evaluate`
+    XPath(String),
+    /// The innermost elements whose text content equals the text.
+    ///
+    /// Whitespace is normalized before comparing.
+    Text(String),
+    /// The innermost elements whose text content contains the text, ignoring
+    /// case.
+    PartialText(String),
+    /// Elements with the ARIA role and, if set, the accessible name, as
+    /// computed by chromium's accessibility tree.
+    Role { role: String, name: Option<String> },
+}
+
+impl Selector {
+    pub fn css(selector: impl Into<String>) -> Self {
+        Selector::Css(selector.into())
+    }
+
+    pub fn pierce(selector: impl Into<String>) -> Self {
+        Selector::Pierce(selector.into())
+    }
+
+    pub fn xpath(expression: impl Into<String>) -> Self {
+        Selector::XPath(expression.into())
+    }
+
+    pub fn text(text: impl Into<String>) -> Self {
+        Selector::Text(text.into())
+    }
+
+    pub fn partial_text(text: impl Into<String>) -> Self {
+        Selector::PartialText(text.into())
+    }
+
+    /// Matches all elements with the `role`, regardless of their name.
+    pub fn role(role: impl Into<String>) -> Self {
+        Selector::Role {
+            role: role.into(),
+            name: None,
+        }
+    }
+
+    /// Matches all elements with the `role` and the accessible `name`
+    pub fn role_with_name(role: impl Into<String>, name: impl Into<String>) -> Self {
+        Selector::Role {
+            role: role.into(),
+            name: Some(name.into()),
+        }
+    }
+
+    /// The function declaration that returns all nodes matching this selector
+    /// within the `this` node, `None` for selectors that are not resolved via
+    /// javascript.
+    pub(crate) fn query_all_fn(&self) -> Option<String> {
+        let f = match self {
+            Selector::Pierce(selector) => {
+                PIERCE_QUERY_ALL.replace("SELECTOR", &serde_json::to_string(selector).unwrap())
+            }
+            Selector::XPath(expr) => {
+                XPATH_QUERY_ALL.replace("XPATH", &serde_json::to_string(expr).unwrap())
+            }
+            Selector::Text(text) => TEXT_QUERY_ALL
+                .replace("EXACT", "true")
+                .replace("TEXT", &serde_json::to_string(text).unwrap()),
+            Selector::PartialText(text) => TEXT_QUERY_ALL
+                .replace("EXACT", "false")
+                .replace("TEXT", &serde_json::to_string(text).unwrap()),
+            Selector::Css(_) | Selector::Role { .. } => return None,
+        };
+        Some(f)
+    }
+
+    /// The javascript expression that evaluates to the first node matching
+    /// this selector within the `root` node, `None` for selectors that are
+    /// not resolved via javascript.
+    pub(crate) fn query_first_js(&self) -> Option<String> {
+        match self {
+            Selector::Css(selector) => Some(format!(
+                "root.querySelector({})",
+                serde_json::to_string(selector).unwrap()
+            )),
+            selector => selector
+                .query_all_fn()
+                .map(|query_all| format!("({}).call(root)[0]", query_all)),
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Selector::Css(s) => write!(f, "css={}", s),
+            Selector::Pierce(s) => write!(f, "pierce={}", s),
+            Selector::XPath(s) => write!(f, "xpath={}", s),
+            Selector::Text(s) => write!(f, "text=\"{}\"", s),
+            Selector::PartialText(s) => write!(f, "text={}", s),
+            Selector::Role { role, name: None } => write!(f, "role={}", role),
+            Selector::Role {
+                role,
+                name: Some(name),
+            } => write!(f, "role={}[name=\"{}\"]", role, name),
+        }
+    }
+}
+
+impl From<&str> for Selector {
+    fn from(s: &str) -> Self {
+        Selector::Css(s.to_string())
+    }
+}
+
+impl From<String> for Selector {
+    fn from(s: String) -> Self {
+        Selector::Css(s)
+    }
+}
+
+impl From<&String> for Selector {
+    fn from(s: &String) -> Self {
+        Selector::Css(s.clone())
+    }
+}
+
+const PIERCE_QUERY_ALL: &str = "function() {
+    const selector = SELECTOR;
+    const result = [];
+    const visit = (root) => {
+        for (const el of root.querySelectorAll(selector)) result.push(el);
+        for (const el of root.querySelectorAll('*')) {
+            if (el.shadowRoot) visit(el.shadowRoot);
+        }
+    };
+    visit(this);
+    return result;
+}";
+
+const XPATH_QUERY_ALL: &str = "function() {
+    const doc = this.ownerDocument || this;
+    const snapshot = doc.evaluate(XPATH, this, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+    const result = [];
+    for (let i = 0; i < snapshot.snapshotLength; i++) result.push(snapshot.snapshotItem(i));
+    return result;
+}";
+
+/// Finds the innermost elements matching the text, including those in open
+/// shadow roots
+const TEXT_QUERY_ALL: &str = "function() {
+    const exact = EXACT;
+    const normalize = (s) => s.replace(/\\s+/g, ' ').trim();
+    const needle = exact ? normalize(TEXT) : normalize(TEXT).toLowerCase();
+    const matches = (el) => {
+        const content = normalize(el.textContent || '');
+        return exact ? content === needle : content.toLowerCase().includes(needle);
+    };
+    const skip = ['SCRIPT', 'STYLE', 'NOSCRIPT', 'TEMPLATE', 'HEAD', 'TITLE'];
+    const result = [];
+    const visit = (root) => {
+        for (const el of root.querySelectorAll('*')) {
+            if (el.shadowRoot) visit(el.shadowRoot);
+            if (skip.includes(el.nodeName)) continue;
+            if (matches(el) && !Array.from(el.children).some(matches)) result.push(el);
+        }
+    };
+    visit(this);
+    return result;
+}";
@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use chromiumoxide_cdp::cdp::browser_protocol::network::{Cookie, CookieParam};
+
+use crate::cookies::cookie_param;
+use crate::error::Result;
+
+/// A snapshot of the cookies and the web storage of a page that can be
+/// persisted as JSON, e.g. to restore a logged-in session in a later run.
+///
+/// `Page::storage_state` only collects the web storage of the origin of the
+/// main frame. The states of several origins can be combined by extending
+/// `origins` with those of pages that were navigated to them.
+///
+/// # Example persist the storage state and restore it in a new page
+///
+/// ```no_run
+/// # use chromiumoxide::browser::Browser;
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::storage::StorageState;
+/// # use chromiumoxide::error::Result;
+/// # async fn demo(browser: Browser, page: Page) -> Result<()> {
+///     page.storage_state().await?.save("state.json")?;
+///
+///     let state = StorageState::load("state.json")?;
+///     let page = browser
+///         .new_page_with_storage_state("https://example.com", &state)
+///         .await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StorageState {
+    pub cookies: Vec<Cookie>,
+    pub origins: Vec<OriginState>,
+}
+
+impl StorageState {
+    /// Parses a state previously serialized with `StorageState::to_json`
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serializes the state as JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Reads the state from the JSON file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Writes the state as JSON to the file
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// The cookies as parameters to set them again
+    pub fn cookie_params(&self) -> Vec<CookieParam> {
+        self.cookies.iter().map(cookie_param).collect()
+    }
+
+    /// The script that restores the web storage of the origins when a
+    /// document of one of them is loaded.
+    ///
+    /// Only items that are missing are set, so changes the page makes to the
+    /// restored items are kept on subsequent loads of the same origin.
+    pub(crate) fn init_script(&self) -> Result<String> {
+        Ok(format!(
+            "({})({})",
+            RESTORE_WEB_STORAGE,
+            serde_json::to_string(&self.origins)?
+        ))
+    }
+}
+
+/// The web storage of a single origin
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginState {
+    /// The origin, like `https://example.com`
+    pub origin: String,
+    #[serde(default)]
+    pub local_storage: Vec<NameValue>,
+    #[serde(default)]
+    pub session_storage: Vec<NameValue>,
+}
+
+/// A single item of a `localStorage` or `sessionStorage`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+impl NameValue {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+}
+
+/// Collects the web storage of the current document as `OriginState`, or
+/// `null` for opaque origins like `about:blank`.
+pub(crate) const COLLECT_WEB_STORAGE: &str = r#"(() => {
+    if (!location.origin || location.origin === 'null') {
+        return null;
+    }
+    const items = (storage) => {
+        try {
+            return Object.keys(storage).map((name) => ({ name, value: storage.getItem(name) }));
+        } catch (e) {
+            return [];
+        }
+    };
+    return {
+        origin: location.origin,
+        localStorage: items(window.localStorage),
+        sessionStorage: items(window.sessionStorage),
+    };
+})()"#;
+
+const RESTORE_WEB_STORAGE: &str = r#"(origins) => {
+    const state = origins.find((o) => o.origin === location.origin);
+    if (!state) {
+        return;
+    }
+    const restore = (storage, items) => {
+        try {
+            for (const { name, value } of items) {
+                if (storage.getItem(name) === null) {
+                    storage.setItem(name, value);
+                }
+            }
+        } catch (e) {}
+    };
+    restore(window.localStorage, state.localStorage || []);
+    restore(window.sessionStorage, state.sessionStorage || []);
+}"#;
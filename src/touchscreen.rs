@@ -0,0 +1,115 @@
+use std::sync::Arc;
+
+use chromiumoxide_cdp::cdp::browser_protocol::input::DispatchTouchEventType;
+
+use crate::error::Result;
+use crate::handler::PageInner;
+use crate::layout::Point;
+
+/// Dispatches touch events to a page.
+///
+/// Pages only react to touch events if touch emulation is enabled, see
+/// `Viewport::has_touch`.
+///
+/// # Example zoom in with two fingers
+///
+/// ```no_run
+/// # use chromiumoxide::page::Page;
+/// # use chromiumoxide::error::Result;
+/// # use chromiumoxide::layout::Point;
+/// # async fn demo(page: Page) -> Result<()> {
+///     page.touchscreen()
+///         .pinch(Point::new(200., 300.), 50., 150., 10)
+///         .await?;
+///     # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Touchscreen {
+    tab: Arc<PageInner>,
+}
+
+impl Touchscreen {
+    pub(crate) fn new(tab: Arc<PageInner>) -> Self {
+        Self { tab }
+    }
+
+    /// Starts touching the page with a finger at each of the points.
+    ///
+    /// The index of a point is used as the identifier of its touch.
+    pub async fn start(&self, points: &[Point]) -> Result<&Self> {
+        self.tab
+            .dispatch_touch(DispatchTouchEventType::TouchStart, points)
+            .await?;
+        Ok(self)
+    }
+
+    /// Moves the active touches to the points
+    pub async fn move_to(&self, points: &[Point]) -> Result<&Self> {
+        self.tab
+            .dispatch_touch(DispatchTouchEventType::TouchMove, points)
+            .await?;
+        Ok(self)
+    }
+
+    /// Lifts all fingers
+    pub async fn end(&self) -> Result<&Self> {
+        self.tab
+            .dispatch_touch(DispatchTouchEventType::TouchEnd, &[])
+            .await?;
+        Ok(self)
+    }
+
+    /// Taps the point with a single finger
+    pub async fn tap(&self, point: Point) -> Result<&Self> {
+        self.start(&[point]).await?.end().await
+    }
+
+    /// Touches `from` and moves the finger to `to` in `steps` intermediate
+    /// `touchMove` events, before lifting it.
+    pub async fn swipe(&self, from: Point, to: Point, steps: usize) -> Result<&Self> {
+        self.start(&[from]).await?;
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let ratio = step as f64 / steps as f64;
+            self.move_to(&[interpolate(from, to, ratio)]).await?;
+        }
+        self.end().await
+    }
+
+    /// Pinches with two fingers placed horizontally around the `center`,
+    /// moving them from `start_distance` to `end_distance` apart in `steps`
+    /// intermediate `touchMove` events.
+    ///
+    /// An `end_distance` larger than the `start_distance` zooms in.
+    pub async fn pinch(
+        &self,
+        center: Point,
+        start_distance: f64,
+        end_distance: f64,
+        steps: usize,
+    ) -> Result<&Self> {
+        let fingers = |distance: f64| {
+            [
+                Point::new(center.x - distance / 2., center.y),
+                Point::new(center.x + distance / 2., center.y),
+            ]
+        };
+        self.start(&fingers(start_distance)).await?;
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let ratio = step as f64 / steps as f64;
+            let distance = start_distance + (end_distance - start_distance) * ratio;
+            self.move_to(&fingers(distance)).await?;
+        }
+        self.end().await
+    }
+}
+
+/// The point at `ratio` of the way from `from` to `to`
+fn interpolate(from: Point, to: Point, ratio: f64) -> Point {
+    Point::new(
+        from.x + (to.x - from.x) * ratio,
+        from.y + (to.y - from.y) * ratio,
+    )
+}
@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use crate::handler::REQUEST_TIMEOUT;
+use crate::selector::Selector;
+
+/// How often the predicate of a wait operation is checked inside the page.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Polling {
+    /// Check on every `requestAnimationFrame` callback
+    #[default]
+    Raf,
+    /// Check on every DOM mutation
+    Mutation,
+    /// Check periodically with the given interval
+    Interval(Duration),
+}
+
+impl Polling {
+    /// The javascript representation as understood by [`WAIT_FOR_PREDICATE`]
+    fn to_js(self) -> String {
+        match self {
+            Polling::Raf => "'raf'".to_string(),
+            Polling::Mutation => "'mutation'".to_string(),
+            Polling::Interval(interval) => interval.as_millis().max(1).to_string(),
+        }
+    }
+}
+
+/// Configures how to wait for a selector or function
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaitOptions {
+    /// Only for selectors: wait until the element is present and visible,
+    /// meaning it has a non-empty bounding box and no `visibility: hidden`.
+    pub visible: bool,
+    /// Only for selectors: wait until the element is either not present or
+    /// hidden.
+    pub hidden: bool,
+    /// How long to wait at most, defaults to 30 seconds.
+    pub timeout: Duration,
+    /// How often to check whether waiting is done.
+    ///
+    /// ARIA role selectors are resolved outside of the page and polled every
+    /// 100ms, unless an interval is set.
+    pub polling: Polling,
+}
+
+impl WaitOptions {
+    pub fn visible(mut self, visible: bool) -> Self {
+        self.visible = visible;
+        self
+    }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn polling(mut self, polling: Polling) -> Self {
+        self.polling = polling;
+        self
+    }
+
+    /// Describes what is waited for the `selector`
+    pub(crate) fn describe_selector(&self, selector: &Selector) -> String {
+        if self.visible {
+            format!("selector `{}` to be visible", selector)
+        } else if self.hidden {
+            format!("selector `{}` to be hidden", selector)
+        } else {
+            format!("selector `{}`", selector)
+        }
+    }
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            hidden: false,
+            timeout: Duration::from_millis(REQUEST_TIMEOUT),
+            polling: Polling::default(),
+        }
+    }
+}
+
+impl From<Polling> for WaitOptions {
+    fn from(polling: Polling) -> Self {
+        WaitOptions::default().polling(polling)
+    }
+}
+
+/// The lifecycle event of a document after which loading it is considered
+/// complete
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum WaitUntil {
+    /// The `load` event was fired
+    #[default]
+    Load,
+    /// The `DOMContentLoaded` event was fired
+    DomContentLoaded,
+    /// There were no network connections for at least 500ms
+    NetworkIdle,
+    /// There were no more than 2 network connections for at least 500ms
+    NetworkAlmostIdle,
+}
+
+impl WaitUntil {
+    /// The name of the event as reported by `Page.lifecycleEvent`
+    pub(crate) fn lifecycle_event(self) -> &'static str {
+        match self {
+            WaitUntil::Load => "load",
+            WaitUntil::DomContentLoaded => "DOMContentLoaded",
+            WaitUntil::NetworkIdle => "networkIdle",
+            WaitUntil::NetworkAlmostIdle => "networkAlmostIdle",
+        }
+    }
+}
+
+/// Polls the `predicate` inside the page with the configured `polling`
+/// until it returns a truthy value or the `timeout` in ms exceeded, in which
+/// case `undefined` is returned.
+///
+/// The predicate is executed with `this` bound to the root node.
+///
+/// Based on [puppeteer](https://github.com/puppeteer/puppeteer/blob/v5.3.1/src/common/DOMWorld.ts#L786)
+const WAIT_FOR_PREDICATE: &str = "async function() {
+    const root = this;
+    const predicate = async () => { PREDICATE };
+    const polling = POLLING;
+    const timeout = TIMEOUT;
+    let timedOut = false;
+    if (timeout) setTimeout(() => (timedOut = true), timeout);
+
+    if (polling === 'raf') return await pollRaf();
+    if (polling === 'mutation') return await pollMutation();
+    return await pollInterval(polling);
+
+    async function pollMutation() {
+        const success = await predicate();
+        if (success) return success;
+        let fulfill;
+        const result = new Promise((x) => (fulfill = x));
+        const observer = new MutationObserver(async () => {
+            if (timedOut) {
+                observer.disconnect();
+                fulfill();
+            }
+            const success = await predicate();
+            if (success) {
+                observer.disconnect();
+                fulfill(success);
+            }
+        });
+        observer.observe(document, { childList: true, subtree: true, attributes: true });
+        if (timeout) setTimeout(() => { observer.disconnect(); fulfill(); }, timeout);
+        return result;
+    }
+
+    async function pollRaf() {
+        let fulfill;
+        const result = new Promise((x) => (fulfill = x));
+        await onRaf();
+        return result;
+
+        async function onRaf() {
+            if (timedOut) {
+                fulfill();
+                return;
+            }
+            const success = await predicate();
+            if (success) fulfill(success);
+            else requestAnimationFrame(onRaf);
+        }
+    }
+
+    async function pollInterval(pollInterval) {
+        let fulfill;
+        const result = new Promise((x) => (fulfill = x));
+        await onTimeout();
+        return result;
+
+        async function onTimeout() {
+            if (timedOut) {
+                fulfill();
+                return;
+            }
+            const success = await predicate();
+            if (success) fulfill(success);
+            else setTimeout(onTimeout, pollInterval);
+        }
+    }
+}";
+
+/// Whether the `element` has a non-empty bounding box and no
+/// `visibility: hidden` style.
+///
+/// This is the definition of visibility used by all waits, state queries and
+/// actionability checks.
+pub(crate) const IS_VISIBLE: &str = "(element) => {
+    const style = window.getComputedStyle(element);
+    const rect = element.getBoundingClientRect();
+    return !!style && style.visibility !== 'hidden'
+        && !!(rect.top || rect.bottom || rect.width || rect.height);
+}";
+
+/// Checks whether the first element returned by `QUERY` inside `root`
+/// satisfies the visibility requirements.
+///
+/// Returns the element when waiting for it to appear and `true` when waiting
+/// for it to be hidden.
+const SELECTOR_PREDICATE: &str = "
+    const element = QUERY;
+    const waitForVisible = WAIT_VISIBLE;
+    const waitForHidden = WAIT_HIDDEN;
+    if (!element) return waitForHidden;
+    if (!waitForVisible && !waitForHidden) return element;
+    const isVisible = (IS_VISIBLE)(element);
+    const success = waitForVisible ? isVisible : !isVisible;
+    if (!success) return null;
+    return waitForVisible ? element : true;
+";
+
+/// Evaluates the expression `JS`, if it evaluates to a function, the function
+/// is called.
+const FUNCTION_PREDICATE: &str = "
+    const value = (JS);
+    return typeof value === 'function' ? await value() : value;
+";
+
+/// The function declaration that waits until `predicate` returns a truthy
+/// value
+fn wait_for_predicate(predicate: &str, opts: &WaitOptions) -> String {
+    WAIT_FOR_PREDICATE
+        .replace("POLLING", &opts.polling.to_js())
+        .replace("TIMEOUT", &opts.timeout.as_millis().to_string())
+        .replace("PREDICATE", predicate)
+}
+
+/// The function declaration that waits for the `selector` within the `this`
+/// node, `None` if the selector can't be resolved inside the page.
+pub(crate) fn wait_for_selector_fn(selector: &Selector, opts: &WaitOptions) -> Option<String> {
+    let predicate = SELECTOR_PREDICATE
+        .replace("WAIT_VISIBLE", &opts.visible.to_string())
+        .replace("WAIT_HIDDEN", &opts.hidden.to_string())
+        .replace("IS_VISIBLE", IS_VISIBLE)
+        .replace("QUERY", &selector.query_first_js()?);
+    Some(wait_for_predicate(&predicate, opts))
+}
+
+/// The function declaration that waits for the javascript `js` to evaluate to
+/// a truthy value
+pub(crate) fn wait_for_function_fn(js: &str, opts: &WaitOptions) -> String {
+    wait_for_predicate(&FUNCTION_PREDICATE.replace("JS", js), opts)
+}